@@ -0,0 +1,227 @@
+use std::{env, error::Error, net::SocketAddr, time::Duration};
+
+use dotenv::dotenv;
+use serde::Deserialize;
+use serenity::futures::{SinkExt, StreamExt};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    time::sleep,
+};
+use tokio_tungstenite::{
+    tungstenite::{
+        handshake::server::{ErrorResponse, Request, Response as HandshakeResponse},
+        http::StatusCode,
+        Message,
+    },
+    WebSocketStream,
+};
+
+#[allow(dead_code)]
+#[path = "../ws/response.rs"]
+mod response;
+
+use response::{Event, EventId, ItemData, Response, User};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+const DEFAULT_ADDR: &str = "127.0.0.1:8081";
+const DEFAULT_SPEED: f64 = 1.0;
+const DELAY_BETWEEN_PLAYBACKS_MILLIS: u64 = 10000;
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ScriptedRun {
+    nickname: String,
+    uuid: String,
+    live_account: Option<String>,
+    #[serde(default)]
+    start_delay: i64,
+    event_list: Vec<Event>,
+    #[serde(default)]
+    context_event_list: Vec<Event>,
+    item_data: Option<ItemData>,
+}
+
+fn event(event_id: EventId, minutes: i64, seconds: i64) -> Event {
+    let igt = (minutes * 60 + seconds) * 1000;
+    Event {
+        event_id,
+        rta: igt + 5000,
+        igt,
+    }
+}
+
+fn default_runs() -> Vec<ScriptedRun> {
+    vec![
+        ScriptedRun {
+            nickname: "SathyaPramodh".to_string(),
+            uuid: "f5b0b0b0-0000-4000-8000-000000000001".to_string(),
+            live_account: Some("sathyapramodh".to_string()),
+            start_delay: 0,
+            event_list: vec![
+                event(EventId::RsgEnterNether, 1, 20),
+                event(EventId::RsgEnterBastion, 1, 45),
+                event(EventId::RsgEnterFortress, 4, 10),
+                event(EventId::RsgFirstPortal, 5, 40),
+                event(EventId::RsgEnterStronghold, 7, 5),
+                event(EventId::RsgEnterEnd, 7, 50),
+                event(EventId::RsgCredits, 9, 15),
+            ],
+            context_event_list: vec![
+                event(EventId::RsgObtainCryingObsidian, 2, 30),
+                event(EventId::RsgLootBastion, 3, 0),
+                event(EventId::RsgObtainBlazeRod, 4, 50),
+            ],
+            item_data: None,
+        },
+        ScriptedRun {
+            nickname: "bastionless_runner".to_string(),
+            uuid: "f5b0b0b0-0000-4000-8000-000000000002".to_string(),
+            live_account: None,
+            start_delay: 20000,
+            event_list: vec![
+                event(EventId::RsgEnterNether, 1, 50),
+                event(EventId::RsgEnterFortress, 2, 30),
+                event(EventId::RsgFirstPortal, 5, 55),
+                event(EventId::CommonLeaveWorld, 6, 30),
+            ],
+            context_event_list: vec![event(EventId::RsgObtainBlazeRod, 3, 40)],
+            item_data: None,
+        },
+    ]
+}
+
+async fn load_runs() -> Result<Vec<ScriptedRun>> {
+    let path = match env::var("MOCK_SCRIPT") {
+        Ok(path) => path,
+        Err(_) => return Ok(default_runs()),
+    };
+    let contents = tokio::fs::read_to_string(&path).await?;
+    let runs: Vec<ScriptedRun> = serde_json::from_str(contents.as_str())?;
+    Ok(runs)
+}
+
+fn get_timeline(runs: &[ScriptedRun], started_at: i64) -> Vec<(i64, Response)> {
+    let mut timeline = vec![];
+    for (run_idx, run) in runs.iter().enumerate() {
+        let world_id = format!("mock-{}-{}", started_at, run_idx);
+        for idx in 0..run.event_list.len() {
+            let last_event = &run.event_list[idx];
+            let offset = run.start_delay + last_event.rta;
+            let response = Response {
+                game_version: Some("1.16.1".to_string()),
+                world_id: world_id.to_owned(),
+                event_list: run.event_list[..=idx].to_vec(),
+                context_event_list: run
+                    .context_event_list
+                    .iter()
+                    .filter(|ctx| ctx.rta <= last_event.rta)
+                    .cloned()
+                    .collect(),
+                user: User {
+                    uuid: run.uuid.to_owned(),
+                    live_account: run.live_account.to_owned(),
+                },
+                _is_cheated: false,
+                _is_hidden: false,
+                last_updated: started_at + offset,
+                item_data: run.item_data.to_owned(),
+                nickname: run.nickname.to_owned(),
+            };
+            timeline.push((offset, response));
+        }
+    }
+    timeline.sort_by_key(|(offset, _)| *offset);
+    timeline
+}
+
+// Keeps reading from the socket while waiting, so the pings of the bot are answered.
+async fn wait(ws_stream: &mut WebSocketStream<TcpStream>, millis: u64) -> Result<()> {
+    let delay = sleep(Duration::from_millis(millis));
+    tokio::pin!(delay);
+    loop {
+        tokio::select! {
+            _ = &mut delay => return Ok(()),
+            msg = ws_stream.next() => match msg {
+                Some(Ok(Message::Close(_))) | None => {
+                    return Err("Connection closed by the client.".into())
+                }
+                Some(Ok(_)) => (),
+                Some(Err(err)) => return Err(err.into()),
+            },
+        }
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    addr: SocketAddr,
+    runs: Vec<ScriptedRun>,
+) -> Result<()> {
+    let auth_key = env::var("API_AUTH_KEY").unwrap_or_default();
+    // The error type is dictated by tungstenite's handshake callback.
+    #[allow(clippy::result_large_err)]
+    let check_auth = |request: &Request, response: HandshakeResponse| {
+        let auth = request
+            .headers()
+            .get("auth")
+            .and_then(|value| value.to_str().ok());
+        if auth != Some(auth_key.as_str()) {
+            let mut error_response = ErrorResponse::new(Some("Invalid auth key.".to_string()));
+            *error_response.status_mut() = StatusCode::UNAUTHORIZED;
+            return Err(error_response);
+        }
+        Ok(response)
+    };
+    let mut ws_stream = tokio_tungstenite::accept_hdr_async(stream, check_auth).await?;
+    println!("Accepted connection from: {}.", addr);
+    let speed = match env::var("MOCK_SPEED") {
+        Ok(speed) => speed.parse::<f64>()?,
+        Err(_) => DEFAULT_SPEED,
+    };
+    loop {
+        let started_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_millis() as i64;
+        let mut previous_offset = 0;
+        for (offset, response) in get_timeline(&runs, started_at) {
+            if speed > 0.0 && offset > previous_offset {
+                let delay = (offset - previous_offset) as f64 / speed;
+                wait(&mut ws_stream, delay as u64).await?;
+            }
+            previous_offset = offset;
+            let text = serde_json::to_string(&response)?;
+            ws_stream.send(Message::Text(text)).await?;
+            println!(
+                "Sent event: {:?} for runner: '{}' to: {}.",
+                response.event_list.last().map(|evt| &evt.event_id),
+                response.nickname,
+                addr
+            );
+        }
+        wait(&mut ws_stream, DELAY_BETWEEN_PLAYBACKS_MILLIS).await?;
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+    let addr = env::var("MOCK_WS_ADDR").unwrap_or(DEFAULT_ADDR.to_string());
+    let runs = load_runs().await?;
+    let listener = TcpListener::bind(&addr).await?;
+    println!(
+        "Serving {} scripted runs on ws://{}. Point WS_URL and WS_HOST at it to use it.",
+        runs.len(),
+        addr
+    );
+    loop {
+        let (stream, addr) = listener.accept().await?;
+        let runs = runs.clone();
+        tokio::spawn(async move {
+            match handle_connection(stream, addr, runs).await {
+                Ok(_) => (),
+                Err(err) => eprintln!("Connection error for: {}: {}", addr, err),
+            }
+        });
+    }
+}
@@ -1,17 +1,54 @@
-use serenity::{client::Context, model::id::GuildId};
+use std::{collections::HashMap, time::Instant};
 
-use crate::Result;
+use serenity::{
+    client::Context,
+    model::id::{GuildId, MessageId, UserId},
+};
 
-use super::{guild_data::GuildData, CacheManager, CachedGuilds};
+use crate::{db::Database, handler::ArcMutex, ws::response::EventId, Result};
+
+use super::{
+    consts::{DM_RATE_LIMIT_COUNT, DM_RATE_LIMIT_SECS, RUN_CACHE_EXPIRY_SECS},
+    guild_data::GuildData,
+    runs::{CachedRuns, RunData, SentMessage},
+    CacheManager, CachedGuilds,
+};
 
 impl CacheManager {
-    pub fn new() -> Self {
+    pub fn new(db: Database) -> Self {
         let cache = CachedGuilds::new();
-        Self { cache }
+        let runs = CachedRuns::new();
+        Self {
+            cache,
+            runs,
+            db,
+            sent_dms: HashMap::new(),
+            leaderboard_locks: HashMap::new(),
+        }
+    }
+
+    // Users get at most DM_RATE_LIMIT_COUNT DMs in every DM_RATE_LIMIT_SECS window.
+    pub fn allow_dm(&mut self, user_id: UserId) -> bool {
+        let sent_dms = self.sent_dms.entry(user_id).or_default();
+        sent_dms.retain(|sent_at| sent_at.elapsed().as_secs() < DM_RATE_LIMIT_SECS);
+        sent_dms.len() < DM_RATE_LIMIT_COUNT
+    }
+
+    // Only DMs that were delivered count towards the rate limit.
+    pub fn record_dm(&mut self, user_id: UserId) {
+        self.sent_dms
+            .entry(user_id)
+            .or_default()
+            .push(Instant::now());
+    }
+
+    // Writes to the leaderboard channel of a guild are made one at a time.
+    pub fn leaderboard_lock(&mut self, guild_id: GuildId) -> ArcMutex<()> {
+        self.leaderboard_locks.entry(guild_id).or_default().clone()
     }
 
-    pub async fn add_or_update_guild(&mut self, ctx: &Context, guild_id: GuildId) -> Result<()> {
-        let guild_data = match GuildData::new(ctx, guild_id).await {
+    pub fn add_or_update_guild(&mut self, ctx: &Context, guild_id: GuildId) -> Result<()> {
+        let guild_data = match GuildData::new(ctx, guild_id, &self.db) {
             Ok(data) => data,
             Err(err) => return Err(format!("CacheManagerError: {}", err).into()),
         };
@@ -30,4 +67,91 @@ impl CacheManager {
         };
         Ok(())
     }
+
+    pub fn remove_expired_runs(&mut self) {
+        self.runs
+            .retain(|_, run_data| !run_data.is_expired(RUN_CACHE_EXPIRY_SECS));
+    }
+
+    // Events like leaving the world can happen more than once in a run, so they are
+    // tracked per occurrence.
+    pub fn is_dispatched(&self, world_id: &String, event_id: &EventId, occurrence: usize) -> bool {
+        match self.runs.get(world_id) {
+            Some(run_data) => run_data
+                .dispatched_events
+                .iter()
+                .any(|(id, count)| id == event_id && *count == occurrence),
+            None => false,
+        }
+    }
+
+    pub fn mark_dispatched(&mut self, world_id: &String, event_id: &EventId, occurrence: usize) {
+        let run_data = self
+            .runs
+            .entry(world_id.to_owned())
+            .or_insert_with(RunData::new);
+        run_data
+            .dispatched_events
+            .push((event_id.to_owned(), occurrence));
+        run_data.last_seen = Instant::now();
+    }
+
+    pub fn untrack_ended_messages(
+        &mut self,
+        world_id: &String,
+        guild_id: GuildId,
+        ended: &[MessageId],
+    ) {
+        let run_data = match self.runs.get_mut(world_id) {
+            Some(run_data) => run_data,
+            None => return,
+        };
+        if let Some(sent) = run_data.sent.get_mut(&guild_id) {
+            sent.retain(|sent| !ended.contains(&sent.message_id));
+            if sent.is_empty() {
+                run_data.sent.remove(&guild_id);
+            }
+        }
+        let is_run_message_ended = run_data.messages.get(&guild_id).is_some_and(|run_message| {
+            match run_message.message_id {
+                Some(message_id) => ended.contains(&message_id),
+                None => true,
+            }
+        });
+        if is_run_message_ended {
+            run_data.messages.remove(&guild_id);
+        }
+    }
+
+    pub fn track_sent_message(
+        &mut self,
+        world_id: &String,
+        guild_id: GuildId,
+        sent_message: SentMessage,
+        is_evolving: bool,
+    ) {
+        let run_data = match self.runs.get_mut(world_id) {
+            Some(run_data) => run_data,
+            None => {
+                return eprintln!(
+                    "CacheManagerError: track message for world id: '{}' in guild id: {}",
+                    world_id, guild_id
+                )
+            }
+        };
+        if is_evolving && !sent_message.is_ping {
+            if let Some(run_message) = run_data.messages.get_mut(&guild_id) {
+                run_message.channel_id = sent_message.channel_id;
+                run_message.message_id = Some(sent_message.message_id);
+            }
+        }
+        let sent = run_data.sent.entry(guild_id).or_default();
+        match sent
+            .iter_mut()
+            .find(|sent| sent.message_id == sent_message.message_id)
+        {
+            Some(sent) => *sent = sent_message,
+            None => sent.push(sent_message),
+        };
+    }
 }
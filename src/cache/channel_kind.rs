@@ -0,0 +1,108 @@
+use serenity::{
+    client::Context,
+    model::id::{ChannelId, GuildId},
+};
+
+use crate::{db::Database, Result};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ChannelKind {
+    Pace,
+    Finish,
+    Leaderboard,
+    Config,
+    Audit,
+}
+
+impl ChannelKind {
+    pub const ALL: [ChannelKind; 5] = [
+        ChannelKind::Pace,
+        ChannelKind::Finish,
+        ChannelKind::Leaderboard,
+        ChannelKind::Config,
+        ChannelKind::Audit,
+    ];
+
+    pub fn from_command_param(param: &str) -> Option<ChannelKind> {
+        match param {
+            "pace" => Some(ChannelKind::Pace),
+            "finish" => Some(ChannelKind::Finish),
+            "leaderboard" => Some(ChannelKind::Leaderboard),
+            "config" => Some(ChannelKind::Config),
+            "audit" => Some(ChannelKind::Audit),
+            _ => None,
+        }
+    }
+
+    pub fn desc(&self) -> String {
+        match self {
+            ChannelKind::Pace => "Pace",
+            ChannelKind::Finish => "Finish",
+            ChannelKind::Leaderboard => "Leaderboard",
+            ChannelKind::Config => "Config",
+            ChannelKind::Audit => "Audit",
+        }
+        .to_string()
+    }
+
+    pub fn setting_key(&self) -> String {
+        match self {
+            ChannelKind::Pace => "pace_channel",
+            ChannelKind::Finish => "finish_channel",
+            ChannelKind::Leaderboard => "leaderboard_channel",
+            ChannelKind::Config => "config_channel",
+            ChannelKind::Audit => "audit_channel",
+        }
+        .to_string()
+    }
+
+    // The channel names the bot used before channels could be configured.
+    pub fn fallback_name(&self) -> Option<&'static str> {
+        match self {
+            ChannelKind::Pace => Some("pacemanbot"),
+            ChannelKind::Leaderboard => Some("pacemanbot-runner-leaderboard"),
+            ChannelKind::Config => Some("pacemanbot-runner-names"),
+            ChannelKind::Finish | ChannelKind::Audit => None,
+        }
+    }
+
+    // A bound channel wins over the name lookup, unless it doesn't exist anymore.
+    pub fn resolve(
+        &self,
+        ctx: &Context,
+        guild_id: GuildId,
+        db: &Database,
+    ) -> Result<Option<ChannelId>> {
+        let channels = match ctx.cache.guild_channels(guild_id) {
+            Some(channels) => channels,
+            None => {
+                return Err(
+                    format!("ChannelKindError: get channels from guild id: {}", guild_id).into(),
+                )
+            }
+        };
+        if let Some(channel_id) = db
+            .get_setting(guild_id, self.setting_key().as_str())?
+            .and_then(|id| id.parse::<u64>().ok())
+        {
+            if channels.contains_key(&ChannelId(channel_id)) {
+                return Ok(Some(ChannelId(channel_id)));
+            }
+            eprintln!(
+                "ChannelKindError: bound {} channel id: {} does not exist in guild id: {}",
+                self.desc(),
+                channel_id,
+                guild_id
+            );
+        }
+        let fallback_name = match self.fallback_name() {
+            Some(name) => name,
+            None => return Ok(None),
+        };
+        let channel_id = channels
+            .iter()
+            .find(|c| c.name == fallback_name)
+            .map(|c| c.id);
+        Ok(channel_id)
+    }
+}
@@ -0,0 +1,5 @@
+pub const RUN_CACHE_EXPIRY_SECS: u64 = 3 * 60 * 60;
+pub const DM_RATE_LIMIT_COUNT: usize = 5;
+pub const DM_RATE_LIMIT_SECS: u64 = 10 * 60;
+pub const LEADERBOARD_PAGE_LIMIT: usize = 2000;
+pub const LEADERBOARD_ROLLOVER_INTERVAL_SECS: u64 = 5 * 60;
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+use super::split::Split;
+
+pub const TEMPLATE_PLACEHOLDERS: &[&str] = &[
+    "runner",
+    "split",
+    "igt",
+    "rta",
+    "items",
+    "stats_link",
+    "live",
+    "run_type",
+    "bastionless",
+    "timestamp",
+];
+
+// Discord's limits for the name and the value of an embed field, the number of
+// fields and the length of a whole embed.
+const FIELD_NAME_LIMIT: usize = 256;
+const FIELD_VALUE_LIMIT: usize = 1024;
+const EMBED_FIELD_LIMIT: usize = 25;
+const EMBED_LENGTH_LIMIT: usize = 6000;
+
+// With evolving messages, every split of a run gets a field above the template
+// fields and a reset adds one more below them.
+const RUN_SPLIT_FIELDS: usize = 7;
+const RUN_END_FIELD_LEN: usize = 64;
+const TEMPLATE_FIELD_LIMIT: usize = EMBED_FIELD_LIMIT - RUN_SPLIT_FIELDS - 1;
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct TemplateField {
+    pub name: String,
+    pub value: String,
+    #[serde(default)]
+    pub inline: bool,
+}
+
+impl TemplateField {
+    fn new(name: &str, value: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.to_string(),
+            inline: false,
+        }
+    }
+}
+
+// Every part of the template is optional, so guilds only have to specify what they
+// want to change.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct EmbedTemplate {
+    pub pace_title: String,
+    pub finish_title: String,
+    pub pace_fields: Vec<TemplateField>,
+    pub finish_fields: Vec<TemplateField>,
+    pub colours: HashMap<String, String>,
+}
+
+impl Default for EmbedTemplate {
+    fn default() -> Self {
+        Self {
+            pace_title: "{live} {igt} - {split}".to_string(),
+            finish_title: "{live} {igt} - Finish".to_string(),
+            pace_fields: vec![
+                TemplateField::new("Splits", "[Link]({stats_link})"),
+                TemplateField::new("Time", "<t:{timestamp}:R>"),
+                TemplateField::new("Items", "{items}"),
+                TemplateField::new("Bastionless", "{bastionless}"),
+            ],
+            finish_fields: vec![
+                TemplateField::new("Splits", "[Link]({stats_link})"),
+                TemplateField::new("Time", "<t:{timestamp}:R>"),
+            ],
+            colours: HashMap::new(),
+        }
+    }
+}
+
+fn check_placeholders(text: &str) -> Result<()> {
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => return Err(format!("EmbedTemplateError: unclosed '{{' in: '{}'.", text).into()),
+        };
+        let placeholder = &rest[start + 1..end];
+        if !TEMPLATE_PLACEHOLDERS.contains(&placeholder) {
+            return Err(format!(
+                "EmbedTemplateError: unknown placeholder: '{{{}}}'. Valid placeholders are: {}.",
+                placeholder,
+                TEMPLATE_PLACEHOLDERS
+                    .iter()
+                    .map(|p| format!("{{{}}}", p))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+            .into());
+        }
+        rest = &rest[end + 1..];
+    }
+    Ok(())
+}
+
+// The longest value each placeholder can take, e.g. runner names have at most 16
+// characters and the item counts include the emoji ids.
+fn max_placeholder_len(placeholder: &str) -> usize {
+    match placeholder {
+        "runner" => 16,
+        "split" | "igt" | "rta" | "run_type" | "timestamp" => 20,
+        "live" => 12,
+        "bastionless" => 3,
+        _ => 100,
+    }
+}
+
+// The length of the text when it is rendered with the longest values.
+fn max_len(text: &str) -> usize {
+    let values = TEMPLATE_PLACEHOLDERS
+        .iter()
+        .map(|placeholder| (*placeholder, "x".repeat(max_placeholder_len(placeholder))))
+        .collect::<HashMap<_, _>>();
+    EmbedTemplate::render(text, &values).chars().count()
+}
+
+fn check_length(text: &str, limit: usize, desc: &str) -> Result<()> {
+    let max_len = max_len(text);
+    if max_len > limit {
+        return Err(format!(
+            "EmbedTemplateError: {} can be up to {} characters long with its placeholders, but Discord allows at most {}.",
+            desc, max_len, limit
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn parse_colour(colour: &str) -> Option<u32> {
+    let hex = colour.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok()
+}
+
+impl EmbedTemplate {
+    pub fn from_json(json: &str) -> Result<Self> {
+        let template: EmbedTemplate = match serde_json::from_str(json) {
+            Ok(template) => template,
+            Err(err) => {
+                return Err(format!("EmbedTemplateError: parse json due to: {}", err).into())
+            }
+        };
+        template.validate()?;
+        Ok(template)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        // Titles are sent as the name of the first field, which can't be empty.
+        for (title, desc) in [
+            (&self.pace_title, "pace_title"),
+            (&self.finish_title, "finish_title"),
+        ] {
+            check_placeholders(title)?;
+            let empty_values = ["items", "live", "bastionless"]
+                .iter()
+                .map(|placeholder| (*placeholder, String::new()))
+                .collect::<HashMap<_, _>>();
+            if Self::render(title, &empty_values).is_empty() {
+                return Err(format!(
+                    "EmbedTemplateError: {} can end up empty, as {{items}}, {{live}} and {{bastionless}} are empty for some runs.",
+                    desc
+                )
+                .into());
+            }
+            check_length(title, FIELD_NAME_LIMIT, desc)?;
+        }
+        let max_title_len = max_len(&self.pace_title).max(max_len(&self.finish_title));
+        for fields in [&self.pace_fields, &self.finish_fields] {
+            if fields.len() > TEMPLATE_FIELD_LIMIT {
+                return Err(format!(
+                    "EmbedTemplateError: a template can have at most {} fields.",
+                    TEMPLATE_FIELD_LIMIT
+                )
+                .into());
+            }
+            for field in fields.iter() {
+                if field.name.trim().is_empty() {
+                    return Err("EmbedTemplateError: field names can't be empty.".into());
+                }
+                check_placeholders(&field.name)?;
+                check_placeholders(&field.value)?;
+                check_length(&field.name, FIELD_NAME_LIMIT, "a field name")?;
+                check_length(&field.value, FIELD_VALUE_LIMIT, "a field value")?;
+            }
+            // The author is the runner name and every split of the run uses its title.
+            let embed_len = max_placeholder_len("runner")
+                + RUN_SPLIT_FIELDS * max_title_len
+                + fields
+                    .iter()
+                    .map(|field| max_len(&field.name) + max_len(&field.value))
+                    .sum::<usize>()
+                + RUN_END_FIELD_LEN;
+            if embed_len > EMBED_LENGTH_LIMIT {
+                return Err(format!(
+                    "EmbedTemplateError: messages can be up to {} characters long with this template, but Discord allows at most {}.",
+                    embed_len, EMBED_LENGTH_LIMIT
+                )
+                .into());
+            }
+        }
+        for (split, colour) in self.colours.iter() {
+            if split != "default" && Split::from_command_param(split).is_none() {
+                return Err(format!(
+                    "EmbedTemplateError: unknown split: '{}' for colour. Use a split like 'blind' or 'default'.",
+                    split
+                )
+                .into());
+            }
+            if parse_colour(colour).is_none() {
+                return Err(format!(
+                    "EmbedTemplateError: invalid colour: '{}', expected a hex colour like '#ff8800'.",
+                    colour
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn colour(&self, split: &Split) -> Option<u32> {
+        let split_param = match split {
+            Split::Nether => "nether",
+            Split::FirstStructure => "first_structure",
+            Split::SecondStructure => "second_structure",
+            Split::Blind => "blind",
+            Split::EyeSpy => "eye_spy",
+            Split::EndEnter => "end_enter",
+            Split::Finish => "finish",
+        };
+        self.colours
+            .get(split_param)
+            .or(self.colours.get("default"))
+            .and_then(|colour| parse_colour(colour))
+    }
+
+    pub fn render(text: &str, values: &HashMap<&str, String>) -> String {
+        let mut rendered = text.to_string();
+        for (placeholder, value) in values.iter() {
+            rendered = rendered.replace(&format!("{{{}}}", placeholder), value);
+        }
+        rendered.trim().to_string()
+    }
+
+    // Fields that end up without a value are left out, e.g. "Items" for runs without
+    // item data.
+    pub fn render_fields(
+        fields: &[TemplateField],
+        values: &HashMap<&str, String>,
+    ) -> Vec<(String, String, bool)> {
+        fields
+            .iter()
+            .map(|field| {
+                (
+                    Self::render(&field.name, values),
+                    Self::render(&field.value, values),
+                    field.inline,
+                )
+            })
+            .filter(|(name, value, _)| !name.is_empty() && !value.is_empty())
+            .collect()
+    }
+}
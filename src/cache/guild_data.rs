@@ -5,22 +5,58 @@ use serenity::{
     model::id::{ChannelId, GuildId},
 };
 
-use crate::{utils::extract_name_and_splits_from_line::extract_name_and_splits_from_line, Result};
+use crate::{
+    db::{
+        consts::{
+            EMBED_TEMPLATE_SETTING, EVOLVING_MESSAGES_SETTING, LEADERBOARD_PERIOD_SETTING,
+            RESET_PING_WINDOW_SETTING,
+        },
+        Database,
+    },
+    dispatcher::run_info::RunType,
+    rules::GuildRule,
+    utils::format_uuid::format_uuid,
+    Result,
+};
 
-use super::{players::Players, role_data::RoleData};
+use super::{
+    channel_kind::ChannelKind,
+    embed_template::EmbedTemplate,
+    item_requirement::ItemRequirement,
+    leaderboard::LeaderboardPeriod,
+    players::{PlayerSplitsData, Players},
+    role_data::RoleData,
+    route::Route,
+    split::Split,
+    subscription::Subscription,
+};
 
 #[derive(Debug)]
 pub struct GuildData {
     pub name: String,
     pub pace_channel: ChannelId,
+    pub finish_channel: ChannelId,
     pub lb_channel: Option<ChannelId>,
+    pub config_channel: Option<ChannelId>,
+    pub audit_channel: Option<ChannelId>,
     pub players: Players,
+    pub runner_uuids: HashMap<String, String>,
     pub is_private: bool,
     pub roles: Vec<RoleData>,
+    pub evolving_messages: bool,
+    pub reset_ping_window: Option<u64>,
+    pub item_requirements: Vec<ItemRequirement>,
+    pub rules: Vec<GuildRule>,
+    pub routes: Vec<Route>,
+    pub subscriptions: Vec<Subscription>,
+    pub embed_template: EmbedTemplate,
+    pub leaderboard_period: Option<LeaderboardPeriod>,
 }
 
 impl GuildData {
-    pub async fn new(ctx: &Context, guild_id: GuildId) -> Result<Self> {
+    // Only reads from the Discord cache and the database, so it is safe to call while
+    // the cache manager is locked.
+    pub fn new(ctx: &Context, guild_id: GuildId, db: &Database) -> Result<Self> {
         let guild = match ctx.cache.guild(guild_id) {
             Some(name) => name,
             None => {
@@ -33,57 +69,51 @@ impl GuildData {
         };
         let name = guild.name;
 
-        let channels = match ctx.cache.guild_channels(guild_id) {
-            Some(channels) => channels,
+        let pace_channel = match ChannelKind::Pace.resolve(ctx, guild_id, db)? {
+            Some(channel_id) => channel_id,
             None => {
-                return Err(
-                    format!("GuildDataError: get channels from guild name: {}", name,).into(),
+                return Err(format!(
+                    "GuildDataError: find #pacemanbot or a configured pace channel in guild name: {}",
+                    name,
                 )
+                .into());
             }
         };
-        let pace_channel = match channels.iter().find(|c| c.name == "pacemanbot") {
-            Some(channel) => channel.id,
-            None => {
-                return Err(
-                    format!("GuildDataError: find #pacemanbot in guild name: {}", name,).into(),
+        let finish_channel = ChannelKind::Finish
+            .resolve(ctx, guild_id, db)?
+            .unwrap_or(pace_channel);
+        let lb_channel = ChannelKind::Leaderboard.resolve(ctx, guild_id, db)?;
+        let config_channel = ChannelKind::Config.resolve(ctx, guild_id, db)?;
+        let audit_channel = ChannelKind::Audit.resolve(ctx, guild_id, db)?;
+        let is_private = config_channel.is_some();
+
+        let players: Players = db.get_players(guild_id)?;
+        let runner_uuids = db.get_runner_uuids(guild_id)?;
+        let evolving_messages = db
+            .get_setting(guild_id, EVOLVING_MESSAGES_SETTING)?
+            .as_deref()
+            == Some("true");
+        let reset_ping_window = db
+            .get_setting(guild_id, RESET_PING_WINDOW_SETTING)?
+            .and_then(|mins| mins.parse::<u64>().ok());
+        let item_requirements = db.get_item_requirements(guild_id)?;
+        let rules = db.get_rules(guild_id)?;
+        let routes = db.get_routes(guild_id)?;
+        let subscriptions = db.get_subscriptions(guild_id)?;
+        let leaderboard_period = db
+            .get_setting(guild_id, LEADERBOARD_PERIOD_SETTING)?
+            .and_then(|period| LeaderboardPeriod::from_setting(period.as_str()));
+        let embed_template = match db.get_setting(guild_id, EMBED_TEMPLATE_SETTING)? {
+            Some(json) => EmbedTemplate::from_json(json.as_str()).unwrap_or_else(|err| {
+                eprintln!(
+                    "GuildDataError: load embed template in guild name: {} due to: {}",
+                    name, err
                 );
-            }
-        };
-        let is_private = channels.iter().any(|c| c.name == "pacemanbot-runner-names");
-        let lb_channel = match channels
-            .iter()
-            .find(|c| c.name == "pacemanbot-runner-leaderboard")
-        {
-            Some(channel) => Some(channel.id),
-            None => None,
+                EmbedTemplate::default()
+            }),
+            None => EmbedTemplate::default(),
         };
 
-        let mut players: Players = HashMap::new();
-        if is_private {
-            let players_channel = channels
-                .iter()
-                .find(|c| c.name == "pacemanbot-runner-names")
-                .unwrap();
-            let messages = players_channel.messages(&ctx.http, |m| m.limit(1)).await?;
-            let first_message = match messages.last() {
-                Some(msg) => msg,
-                None => {
-                    return Err(format!(
-                    "GuildDataError: get first message from #pacemanbot-runner-names in guild name: {}.",
-                    name
-                )
-                    .into())
-                }
-            };
-            for line in first_message.content.split("\n") {
-                if line == "```" || line == "" {
-                    continue;
-                }
-                let (name, splits) = extract_name_and_splits_from_line(line)?;
-                players.insert(name.to_lowercase(), splits);
-            }
-        }
-
         let mut roles: Vec<RoleData> = vec![];
         for role in guild
             .roles
@@ -109,9 +139,61 @@ impl GuildData {
             name,
             is_private,
             pace_channel,
+            finish_channel,
             lb_channel,
+            config_channel,
+            audit_channel,
             players,
+            runner_uuids,
             roles,
+            evolving_messages,
+            reset_ping_window,
+            item_requirements,
+            rules,
+            routes,
+            subscriptions,
+            embed_template,
+            leaderboard_period,
         })
     }
+
+    // Runners that are linked to an account are found by their uuid, everyone else by
+    // their name as long as it doesn't belong to a linked runner.
+    pub fn find_player(&self, uuid: &str, nickname: &str) -> Option<&PlayerSplitsData> {
+        if let Some(name) = self.runner_uuids.get(&format_uuid(uuid)) {
+            return self.players.get(name);
+        }
+        let name = nickname.to_lowercase();
+        if self.runner_uuids.values().any(|linked| linked == &name) {
+            return None;
+        }
+        self.players.get(&name)
+    }
+
+    // Runner specific roles follow the same lookup, so a role keeps pinging for the
+    // linked account and not for whoever picks up its name.
+    pub fn is_runner(&self, runner: &str, uuid: &str, nickname: &str) -> bool {
+        let runner = runner.to_lowercase();
+        if let Some(name) = self.runner_uuids.get(&format_uuid(uuid)) {
+            return name == &runner;
+        }
+        if self.runner_uuids.values().any(|linked| linked == &runner) {
+            return false;
+        }
+        runner == nickname.to_lowercase()
+    }
+
+    // The first matching route wins, otherwise splits go to the pace channel and
+    // completions to the finish channel.
+    pub fn channel_for(&self, split: &Split, runner: &str, run_type: &RunType) -> ChannelId {
+        match self
+            .routes
+            .iter()
+            .find(|route| route.matches(split, runner, run_type))
+        {
+            Some(route) => route.channel_id,
+            None if *split == Split::Finish => self.finish_channel,
+            None => self.pace_channel,
+        }
+    }
 }
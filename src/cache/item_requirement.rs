@@ -0,0 +1,96 @@
+use std::fmt;
+
+use serenity::{model::id::RoleId, prelude::Mentionable};
+
+use crate::ws::response::{Item, ItemData};
+
+use super::{role_data::RoleData, split::Split};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum RequirementTarget {
+    Split(Split),
+    Role(RoleId),
+}
+
+impl RequirementTarget {
+    // Splits are stored by their short name and roles by their id, which can't clash
+    // since split names never contain digits.
+    pub fn from_str(target: &str) -> Option<RequirementTarget> {
+        match target.parse::<u64>() {
+            Ok(role_id) => Some(RequirementTarget::Role(RoleId(role_id))),
+            Err(_) => Some(RequirementTarget::Split(Split::from_str(target)?)),
+        }
+    }
+
+    pub fn to_str(&self) -> String {
+        match self {
+            RequirementTarget::Split(split) => split.to_str(),
+            RequirementTarget::Role(role_id) => role_id.0.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ItemRequirement {
+    pub target: RequirementTarget,
+    pub item: Item,
+    pub min_count: u32,
+}
+
+impl ItemRequirement {
+    pub fn item_from_id(item_id: &str) -> Item {
+        serde_json::from_value(serde_json::Value::String(item_id.to_owned()))
+            .unwrap_or(Item::Unknown(item_id.to_owned()))
+    }
+
+    pub fn item_id(&self) -> String {
+        match serde_json::to_value(&self.item) {
+            Ok(serde_json::Value::String(item_id)) => item_id,
+            _ => String::new(),
+        }
+    }
+
+    pub fn applies_to(&self, role: &RoleData) -> bool {
+        match &self.target {
+            RequirementTarget::Split(split) => role.split == *split,
+            RequirementTarget::Role(role_id) => role.guild_role.id == *role_id,
+        }
+    }
+
+    // Items missing from the item data count as 0, the same as in rules.
+    pub fn is_met(&self, item_data: Option<&ItemData>) -> bool {
+        match item_data {
+            Some(data) => {
+                data.estimated_counts.get(&self.item).copied().unwrap_or(0) >= self.min_count
+            }
+            None => false,
+        }
+    }
+}
+
+impl fmt::Display for ItemRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let item_name = match &self.item {
+            Item::MinecraftEnderPearl => "Ender Pearls".to_string(),
+            Item::MinecraftBlazeRod => "Blaze Rods".to_string(),
+            Item::MinecraftObsidian => "Obsidian".to_string(),
+            Item::Unknown(item_id) => item_id.to_owned(),
+        };
+        match &self.target {
+            RequirementTarget::Split(split) => write!(
+                f,
+                "{} >= {} for {} roles",
+                item_name,
+                self.min_count,
+                split.alt_desc()
+            ),
+            RequirementTarget::Role(role_id) => write!(
+                f,
+                "{} >= {} for {}",
+                item_name,
+                self.min_count,
+                role_id.mention()
+            ),
+        }
+    }
+}
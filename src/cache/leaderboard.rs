@@ -0,0 +1,267 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate};
+
+use crate::{dispatcher::consts::SPECIAL_UNDERSCORE, utils::format_time::format_time};
+
+use super::{consts::LEADERBOARD_PAGE_LIMIT, split::Split};
+
+pub const LEADERBOARD_HEADER: &str = "## Runner Leaderboard";
+pub const LEADERBOARD_ARCHIVE_HEADER: &str = "## Leaderboard Results";
+const LEADERBOARD_ARCHIVE_SIZE: usize = 10;
+const BEST_SPLITS: [Split; 6] = [
+    Split::Nether,
+    Split::FirstStructure,
+    Split::SecondStructure,
+    Split::Blind,
+    Split::EyeSpy,
+    Split::EndEnter,
+];
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LeaderboardEntry {
+    pub uuid: String,
+    pub runner: String,
+    pub split: Split,
+    pub time: u64,
+    pub world_id: Option<String>,
+    pub date: i64,
+}
+
+impl LeaderboardEntry {
+    fn runner_name(&self) -> String {
+        self.runner.replace("_", SPECIAL_UNDERSCORE)
+    }
+
+    fn stats_link(&self) -> Option<String> {
+        self.world_id
+            .as_ref()
+            .map(|world_id| format!("[Run](https://paceman.gg/stats/run/{})", world_id))
+    }
+}
+
+// Holds the best time of every runner for every split they reached.
+#[derive(Debug, Default)]
+pub struct Leaderboard {
+    pub bests: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    pub fn finishes(&self) -> Vec<&LeaderboardEntry> {
+        let mut finishes = self
+            .bests
+            .iter()
+            .filter(|entry| entry.split == Split::Finish)
+            .collect::<Vec<_>>();
+        finishes.sort_by_key(|entry| (entry.time, entry.date));
+        finishes
+    }
+
+    pub fn best(&self, split: &Split) -> Option<&LeaderboardEntry> {
+        self.bests
+            .iter()
+            .filter(|entry| &entry.split == split)
+            .min_by_key(|entry| (entry.time, entry.date))
+    }
+
+    fn render_best_splits(&self) -> Option<String> {
+        let best_splits = BEST_SPLITS
+            .iter()
+            .filter_map(|split| {
+                let entry = self.best(split)?;
+                Some(format!(
+                    "{}: `{}` {}",
+                    split.alt_desc(),
+                    format_time(entry.time),
+                    entry.runner_name()
+                ))
+            })
+            .collect::<Vec<_>>();
+        if best_splits.is_empty() {
+            return None;
+        }
+        Some(format!("### Best Splits\n{}", best_splits.join("\n")))
+    }
+
+    pub fn render_runner(&self, uuid: &str) -> Option<String> {
+        let entries = BEST_SPLITS
+            .iter()
+            .chain([Split::Finish].iter())
+            .filter_map(|split| {
+                self.bests
+                    .iter()
+                    .find(|entry| entry.uuid == uuid && &entry.split == split)
+            })
+            .collect::<Vec<_>>();
+        let mut lines = vec![format!("### {}", entries.first()?.runner_name())];
+        for entry in entries {
+            let mut line = format!(
+                "{}: `{}`\t\t<t:{}:d>",
+                entry.split.alt_desc(),
+                format_time(entry.time),
+                entry.date
+            );
+            if let Some(stats_link) = entry.stats_link() {
+                line.push_str(format!("\t\t{}", stats_link).as_str());
+            }
+            lines.push(line);
+        }
+        Some(lines.join("\n"))
+    }
+
+    // The standings of a finished period are kept short so they fit in one message.
+    pub fn render_archive(&self, period: &LeaderboardPeriod, bounds: (i64, i64)) -> String {
+        let mut lines = vec![
+            LEADERBOARD_ARCHIVE_HEADER.to_string(),
+            format!(
+                "{} leaderboard from {}",
+                period.name(),
+                period.dates(bounds)
+            ),
+            String::new(),
+        ];
+        for (idx, entry) in self
+            .finishes()
+            .iter()
+            .take(LEADERBOARD_ARCHIVE_SIZE)
+            .enumerate()
+        {
+            lines.push(format!(
+                "{}. `{}`\t\t{}",
+                idx + 1,
+                format_time(entry.time),
+                entry.runner_name()
+            ));
+        }
+        if let Some(best_splits) = self.render_best_splits() {
+            lines.push(format!("\n{}", best_splits));
+        }
+        lines.join("\n")
+    }
+
+    // Splits the leaderboard into pages that each fit into a single message.
+    pub fn render(&self, title: &str) -> Vec<String> {
+        let finishes = self.finishes();
+        if finishes.is_empty() {
+            return vec![format!("{}\n\nNo completions yet.", title)];
+        }
+        let mut lines = vec![];
+        for entry in finishes {
+            let mut line = format!(
+                "`{}`\t\t{}\t\t<t:{}:d>",
+                format_time(entry.time),
+                entry.runner_name(),
+                entry.date
+            );
+            if let Some(stats_link) = entry.stats_link() {
+                line.push_str(format!("\t\t{}", stats_link).as_str());
+            }
+            lines.push(line);
+        }
+        if let Some(best_splits) = self.render_best_splits() {
+            lines.push(format!("\n{}", best_splits));
+        }
+        let mut pages = vec![format!("{}\n", title)];
+        for line in lines {
+            let page = pages.last_mut().unwrap();
+            if page.len() + line.len() + 1 > LEADERBOARD_PAGE_LIMIT {
+                pages.push(line.trim_start().to_string());
+            } else {
+                page.push('\n');
+                page.push_str(line.as_str());
+            }
+        }
+        pages
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum LeaderboardPeriod {
+    Weekly,
+    Monthly,
+    // Runs from the start of the first day up to the end of the last day (UTC).
+    Season { start: i64, end: i64 },
+}
+
+impl LeaderboardPeriod {
+    pub fn from_setting(setting: &str) -> Option<LeaderboardPeriod> {
+        match setting.split(':').collect::<Vec<_>>()[..] {
+            ["weekly"] => Some(LeaderboardPeriod::Weekly),
+            ["monthly"] => Some(LeaderboardPeriod::Monthly),
+            ["season", start, end] => Some(LeaderboardPeriod::Season {
+                start: start.parse().ok()?,
+                end: end.parse().ok()?,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn to_setting(&self) -> String {
+        match self {
+            LeaderboardPeriod::Weekly => "weekly".to_string(),
+            LeaderboardPeriod::Monthly => "monthly".to_string(),
+            LeaderboardPeriod::Season { start, end } => format!("season:{}:{}", start, end),
+        }
+    }
+
+    // Takes the first and last day of a season as 'YYYY-MM-DD'.
+    pub fn season(start: &str, end: &str) -> Option<LeaderboardPeriod> {
+        let start = NaiveDate::parse_from_str(start, "%Y-%m-%d").ok()?;
+        let end = NaiveDate::parse_from_str(end, "%Y-%m-%d").ok()? + Duration::days(1);
+        if end <= start {
+            return None;
+        }
+        Some(LeaderboardPeriod::Season {
+            start: start.and_hms_opt(0, 0, 0)?.and_utc().timestamp(),
+            end: end.and_hms_opt(0, 0, 0)?.and_utc().timestamp(),
+        })
+    }
+
+    // Returns the start and end of the period that contains the given time.
+    pub fn bounds(&self, time: i64) -> Option<(i64, i64)> {
+        let date = DateTime::from_timestamp(time, 0)?.date_naive();
+        let (start, end) = match self {
+            LeaderboardPeriod::Weekly => {
+                let start = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+                (start, start + Duration::days(7))
+            }
+            LeaderboardPeriod::Monthly => {
+                let start = date.with_day(1)?;
+                let end = match date.month() {
+                    12 => NaiveDate::from_ymd_opt(date.year() + 1, 1, 1)?,
+                    month => NaiveDate::from_ymd_opt(date.year(), month + 1, 1)?,
+                };
+                (start, end)
+            }
+            LeaderboardPeriod::Season { start, end } => {
+                return (*start <= time && time < *end).then_some((*start, *end))
+            }
+        };
+        Some((
+            start.and_hms_opt(0, 0, 0)?.and_utc().timestamp(),
+            end.and_hms_opt(0, 0, 0)?.and_utc().timestamp(),
+        ))
+    }
+
+    // Returns the first period that hasn't ended yet at the given time.
+    pub fn ending_after(&self, time: i64) -> Option<(i64, i64)> {
+        match self {
+            LeaderboardPeriod::Season { start, end } => (time < *end).then_some((*start, *end)),
+            _ => self.bounds(time),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            LeaderboardPeriod::Weekly => "Weekly",
+            LeaderboardPeriod::Monthly => "Monthly",
+            LeaderboardPeriod::Season { .. } => "Season",
+        }
+    }
+
+    fn dates(&self, (start, end): (i64, i64)) -> String {
+        format!("<t:{}:d> to <t:{}:d>", start, end - 1)
+    }
+
+    pub fn title(&self, bounds: (i64, i64)) -> String {
+        format!("## {} Leaderboard\n{}", self.name(), self.dates(bounds))
+    }
+}
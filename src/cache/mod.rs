@@ -1,16 +1,31 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Instant};
 
 use guild_data::GuildData;
-use serenity::model::id::GuildId;
+use runs::CachedRuns;
+use serenity::model::id::{GuildId, UserId};
+
+use crate::{db::Database, handler::ArcMutex};
 
 pub mod cache;
+pub mod channel_kind;
+pub mod consts;
+pub mod embed_template;
 pub mod guild_data;
+pub mod item_requirement;
+pub mod leaderboard;
 pub mod players;
 pub mod role_data;
+pub mod route;
+pub mod runs;
 pub mod split;
+pub mod subscription;
 
 pub type CachedGuilds = HashMap<GuildId, GuildData>;
 
 pub struct CacheManager {
     pub cache: CachedGuilds,
+    pub runs: CachedRuns,
+    pub db: Database,
+    pub sent_dms: HashMap<UserId, Vec<Instant>>,
+    pub leaderboard_locks: HashMap<GuildId, ArcMutex<()>>,
 }
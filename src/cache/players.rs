@@ -4,14 +4,15 @@ use super::split::Split;
 
 pub type Players = HashMap<String, PlayerSplitsData>;
 
+// All split times are stored in milliseconds.
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub struct PlayerSplitsData {
-    pub first_structure: u8,
-    pub second_structure: u8,
-    pub blind: u8,
-    pub eye_spy: u8,
-    pub end_enter: u8,
-    pub finish: Option<u8>,
+    pub first_structure: u64,
+    pub second_structure: u64,
+    pub blind: u64,
+    pub eye_spy: u64,
+    pub end_enter: u64,
+    pub finish: Option<u64>,
 }
 
 impl PlayerSplitsData {
@@ -26,13 +27,15 @@ impl PlayerSplitsData {
         }
     }
 
-    pub fn get(&self, split: &Split) -> Option<u8> {
+    pub fn get(&self, split: &Split) -> Option<u64> {
         match split {
+            Split::Nether => None,
             Split::FirstStructure => Some(self.first_structure),
             Split::SecondStructure => Some(self.second_structure),
             Split::Blind => Some(self.blind),
             Split::EyeSpy => Some(self.eye_spy),
             Split::EndEnter => Some(self.end_enter),
+            Split::Finish => self.finish,
         }
     }
 }
@@ -0,0 +1,50 @@
+use std::fmt;
+
+use serenity::{model::id::ChannelId, prelude::Mentionable};
+
+use crate::dispatcher::run_info::RunType;
+
+use super::split::Split;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Route {
+    pub id: i64,
+    pub channel_id: ChannelId,
+    pub split: Option<Split>,
+    pub runner: Option<String>,
+    pub run_type: Option<RunType>,
+}
+
+impl Route {
+    // Criteria that are not set match everything.
+    pub fn matches(&self, split: &Split, runner: &str, run_type: &RunType) -> bool {
+        self.split.as_ref().is_none_or(|s| s == split)
+            && self
+                .runner
+                .as_ref()
+                .is_none_or(|r| r.to_lowercase() == runner.to_lowercase())
+            && self.run_type.as_ref().is_none_or(|t| t == run_type)
+    }
+}
+
+impl fmt::Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut criteria = vec![];
+        if let Some(split) = &self.split {
+            criteria.push(split.alt_desc());
+        }
+        if let Some(runner) = &self.runner {
+            criteria.push(format!("runner {}", runner));
+        }
+        if let Some(run_type) = &self.run_type {
+            criteria.push(format!("{} runs", run_type.to_str()));
+        }
+        write!(
+            f,
+            "#{}: {} -> {}",
+            self.id,
+            criteria.join(", "),
+            self.channel_id.mention()
+        )
+    }
+}
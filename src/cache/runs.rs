@@ -0,0 +1,88 @@
+use std::{collections::HashMap, time::Instant};
+
+use serenity::{
+    builder::CreateEmbedAuthor,
+    model::id::{ChannelId, GuildId, MessageId},
+};
+
+use crate::ws::response::EventId;
+
+pub type CachedRuns = HashMap<String, RunData>;
+
+#[derive(Debug)]
+pub struct RunMessage {
+    pub channel_id: ChannelId,
+    pub message_id: Option<MessageId>,
+    pub splits: Vec<String>,
+    pub fields: Vec<(String, String, bool)>,
+}
+
+impl RunMessage {
+    pub fn new(channel_id: ChannelId) -> Self {
+        Self {
+            channel_id,
+            message_id: None,
+            splits: vec![],
+            fields: vec![],
+        }
+    }
+
+    // A run that gets routed to another channel continues in a new message there.
+    pub fn move_to(&mut self, channel_id: ChannelId) {
+        if self.channel_id != channel_id {
+            self.channel_id = channel_id;
+            self.message_id = None;
+        }
+    }
+
+    pub fn add_split(
+        &mut self,
+        split: String,
+        other_fields: Vec<(String, String, bool)>,
+    ) -> Vec<(String, String, bool)> {
+        self.splits.push(split);
+        self.fields = self
+            .splits
+            .iter()
+            .map(|split| (split.to_owned(), String::new(), false))
+            .chain(other_fields)
+            .collect();
+        self.fields.clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SentMessage {
+    pub channel_id: ChannelId,
+    pub message_id: MessageId,
+    pub author: CreateEmbedAuthor,
+    pub fields: Vec<(String, String, bool)>,
+    pub colour: Option<u32>,
+    pub is_ping: bool,
+    pub sent_at: Instant,
+}
+
+#[derive(Debug)]
+pub struct RunData {
+    pub dispatched_events: Vec<(EventId, usize)>,
+    pub last_seen: Instant,
+    pub messages: HashMap<GuildId, RunMessage>,
+    pub sent: HashMap<GuildId, Vec<SentMessage>>,
+    pub fired_rules: HashMap<GuildId, Vec<i64>>,
+}
+
+impl RunData {
+    pub fn new() -> Self {
+        Self {
+            dispatched_events: vec![],
+            last_seen: Instant::now(),
+            messages: HashMap::new(),
+            sent: HashMap::new(),
+            fired_rules: HashMap::new(),
+        }
+    }
+
+    pub fn is_expired(&self, expiry_secs: u64) -> bool {
+        self.last_seen.elapsed().as_secs() >= expiry_secs
+    }
+}
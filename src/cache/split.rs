@@ -2,11 +2,13 @@ use crate::ws::response::EventId;
 
 #[derive(PartialEq, Debug, Clone)]
 pub enum Split {
+    Nether,
     FirstStructure,
     SecondStructure,
     Blind,
     EyeSpy,
     EndEnter,
+    Finish,
 }
 
 pub enum Structure {
@@ -17,31 +19,37 @@ pub enum Structure {
 impl Split {
     pub fn from_str(split: &str) -> Option<Split> {
         match split {
+            "N" => Some(Split::Nether),
             "FS" => Some(Split::FirstStructure),
             "SS" => Some(Split::SecondStructure),
             "B" => Some(Split::Blind),
             "E" => Some(Split::EyeSpy),
             "EE" => Some(Split::EndEnter),
+            "F" => Some(Split::Finish),
             _ => None,
         }
     }
 
     pub fn from_event_id(event_id: &EventId) -> Option<Split> {
         match event_id {
+            EventId::RsgEnterNether => Some(Split::Nether),
             EventId::RsgFirstPortal => Some(Split::Blind),
             EventId::RsgEnterStronghold => Some(Split::EyeSpy),
             EventId::RsgEnterEnd => Some(Split::EndEnter),
+            EventId::RsgCredits => Some(Split::Finish),
             _ => None,
         }
     }
 
     pub fn from_command_param(param: &str) -> Option<Split> {
         match param {
+            "nether" => Some(Split::Nether),
             "first_structure" => Some(Split::FirstStructure),
             "second_structure" => Some(Split::SecondStructure),
             "blind" => Some(Split::Blind),
             "eye_spy" => Some(Split::EyeSpy),
             "end_enter" => Some(Split::EndEnter),
+            "finish" => Some(Split::Finish),
             _ => None,
         }
     }
@@ -49,6 +57,7 @@ impl Split {
     pub fn desc(&self, structure: &Option<Structure>) -> Option<String> {
         Some(
             match self {
+                Split::Nether => "Enter Nether",
                 Split::FirstStructure => match structure {
                     Some(structure) => match structure {
                         Structure::Bastion => "Enter Bastion",
@@ -66,6 +75,7 @@ impl Split {
                 Split::Blind => "First Portal",
                 Split::EyeSpy => "Enter Stronghold",
                 Split::EndEnter => "Enter End",
+                Split::Finish => "Finish",
             }
             .to_string(),
         )
@@ -73,22 +83,26 @@ impl Split {
 
     pub fn alt_desc(&self) -> String {
         match self {
+            Split::Nether => "Nether",
             Split::FirstStructure => "Structure 1",
             Split::SecondStructure => "Structure 2",
             Split::Blind => "Blind",
             Split::EyeSpy => "Eye Spy",
             Split::EndEnter => "End Enter",
+            Split::Finish => "Finish",
         }
         .to_string()
     }
 
     pub fn to_str(&self) -> String {
         match self {
+            Split::Nether => "N",
             Split::FirstStructure => "FS",
             Split::SecondStructure => "SS",
             Split::Blind => "B",
             Split::EyeSpy => "E",
             Split::EndEnter => "EE",
+            Split::Finish => "F",
         }
         .to_string()
     }
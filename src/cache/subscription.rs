@@ -0,0 +1,37 @@
+use std::fmt;
+
+use serenity::model::id::UserId;
+
+use crate::utils::format_time::format_time;
+
+use super::split::Split;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Subscription {
+    pub id: i64,
+    pub user_id: UserId,
+    pub runner: String,
+    pub split: Split,
+    pub time: u64,
+}
+
+impl Subscription {
+    pub fn matches(&self, runner: &str, split: &Split, igt: u64) -> bool {
+        self.runner.to_lowercase() == runner.to_lowercase()
+            && self.split == *split
+            && igt < self.time
+    }
+}
+
+impl fmt::Display for Subscription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{}: {} sub {} {}",
+            self.id,
+            self.runner,
+            format_time(self.time),
+            self.split.alt_desc()
+        )
+    }
+}
@@ -0,0 +1,108 @@
+use serenity::{
+    client::Context,
+    model::{
+        id::{ChannelId, GuildId},
+        prelude::application_command::ApplicationCommandInteraction,
+    },
+    prelude::Mentionable,
+};
+
+use crate::{
+    cache::{channel_kind::ChannelKind, CacheManager},
+    handler::ArcMutex,
+    Result,
+};
+
+pub async fn configure_channels(
+    ctx: &Context,
+    guild_id: GuildId,
+    command: &ApplicationCommandInteraction,
+    cache_manager: ArcMutex<CacheManager>,
+) -> Result<()> {
+    command.defer_ephemeral(&ctx).await?;
+    let mut kind = None;
+    let mut channel_id = None;
+    for option in command.data.options.iter() {
+        let value = match option.value.to_owned() {
+            Some(value) => value,
+            None => {
+                return Err(format!(
+                    "ConfigureChannelsError: get value for option: '{}'.",
+                    option.name
+                )
+                .into())
+            }
+        };
+        let value = match value.as_str() {
+            Some(str) => str.to_owned(),
+            None => {
+                return Err(format!(
+                    "ConfigureChannelsError: parse string for option: '{}'.",
+                    option.name
+                )
+                .into())
+            }
+        };
+        match option.name.as_str() {
+            "kind" => {
+                kind = match ChannelKind::from_command_param(value.as_str()) {
+                    Some(kind) => Some(kind),
+                    None => {
+                        return Err(format!(
+                            "ConfigureChannelsError: Unrecognized channel kind: '{}'.",
+                            value
+                        )
+                        .into())
+                    }
+                }
+            }
+            "channel" => channel_id = Some(ChannelId(value.parse::<u64>()?)),
+            _ => return Err(format!("Unrecognized command option: '{}'", option.name).into()),
+        };
+    }
+    let kind = match kind {
+        Some(kind) => kind,
+        None => return Err("ConfigureChannelsError: get value for kind option.".into()),
+    };
+
+    let mut locked_cache_manager = cache_manager.lock().await;
+    // Without a channel, the binding is removed and the name lookup is used again.
+    match channel_id {
+        Some(channel_id) => locked_cache_manager.db.set_setting(
+            guild_id,
+            kind.setting_key().as_str(),
+            channel_id.0.to_string().as_str(),
+        )?,
+        None => locked_cache_manager
+            .db
+            .remove_setting(guild_id, kind.setting_key().as_str())?,
+    };
+    let mut channels = vec![];
+    for kind in ChannelKind::ALL {
+        let channel = match kind.resolve(ctx, guild_id, &locked_cache_manager.db)? {
+            Some(channel_id) => channel_id.mention().to_string(),
+            None if kind == ChannelKind::Finish => "Same as the pace channel".to_string(),
+            None => "Not set".to_string(),
+        };
+        channels.push(format!("- {}: {}", kind.desc(), channel));
+    }
+    let update_result = locked_cache_manager
+        .add_or_update_guild(ctx, guild_id)
+        .map_err(|err| err.to_string());
+    drop(locked_cache_manager);
+    let response_content = match update_result {
+        Ok(_) => format!(
+            "Updated channels! Current channels:\n{}",
+            channels.join("\n")
+        ),
+        Err(err) => format!(
+            "Updated channels, but the configuration is not valid yet: {}\nCurrent channels:\n{}",
+            err,
+            channels.join("\n")
+        ),
+    };
+    command
+        .edit_original_interaction_response(&ctx.http, |m| m.content(response_content))
+        .await?;
+    Ok(())
+}
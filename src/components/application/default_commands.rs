@@ -1,6 +1,6 @@
 use serenity::{
     client::Context,
-    model::{id::GuildId, prelude::command::CommandOptionType},
+    model::{channel::ChannelType, id::GuildId, prelude::command::CommandOptionType},
 };
 
 use crate::cache::split::Split;
@@ -93,46 +93,391 @@ pub async fn setup_default_commands(ctx: &Context, guild_id: GuildId) {
             .create_option(|option| {
                 option
                     .name("first_structure")
-                    .description("The time for first structure that you want to setup for the runner.")
-                    .kind(CommandOptionType::Integer)
+                    .description("The time for first structure that you want to setup for the runner (m:ss or minutes).")
+                    .kind(CommandOptionType::String)
             })
             .create_option(|option| {
                 option
                     .name("second_structure")
-                    .description("The time for second structure that you want to setup for the runner.")
-                    .kind(CommandOptionType::Integer)
+                    .description("The time for second structure that you want to setup for the runner (m:ss or minutes).")
+                    .kind(CommandOptionType::String)
             })
             .create_option(|option| {
                 option
                     .name("blind")
-                    .description("The time for blind that you want to setup for the runner.")
-                    .kind(CommandOptionType::Integer)
+                    .description("The time for blind that you want to setup for the runner (m:ss or minutes).")
+                    .kind(CommandOptionType::String)
             })
             .create_option(|option| {
                 option
                     .name("eye_spy")
-                    .description("The time for eye spy that you want to setup for the runner.")
-                    .kind(CommandOptionType::Integer)
+                    .description("The time for eye spy that you want to setup for the runner (m:ss or minutes).")
+                    .kind(CommandOptionType::String)
             })
             .create_option(|option| {
                 option
                     .name("end_enter")
-                    .description("The time for end enter that you want to setup for the runner.")
-                    .kind(CommandOptionType::Integer)
+                    .description("The time for end enter that you want to setup for the runner (m:ss or minutes).")
+                    .kind(CommandOptionType::String)
             })
             .create_option(|option| {
                 option
                     .name("finish")
-                    .description("The time for completion that you want to setup for the runner(optional).")
-                    .kind(CommandOptionType::Integer)
+                    .description("The time for completion that you want to setup for the runner (m:ss or minutes, optional).")
+                    .kind(CommandOptionType::String)
             })
         });
         commands.create_application_command(|command| {
             command
             .name("migrate")
             .description(
-                "Migrate the old configuration from first message in #pacemanbot-runner-names."
+                "Import the runner configuration from the first message in #pacemanbot-runner-names."
+            )
+        });
+        commands.create_application_command(|command| {
+            command
+            .name("evolving_messages")
+            .description(
+                "Keep each run in a single message that is edited with every new split.",
+            )
+            .create_option(|option| {
+                option
+                    .name("enabled")
+                    .description("Whether runs should be kept in a single message.")
+                    .required(true)
+                    .kind(CommandOptionType::Boolean)
+            })
+        });
+        commands.create_application_command(|command| {
+            command
+            .name("item_requirements")
+            .description(
+                "Only ping roles when the runner has enough of an item.",
+            )
+            .create_option(|option| {
+                option
+                    .name("action")
+                    .description("Action to perform out of 'add_or_update' or 'remove'.")
+                    .required(true)
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Add or Update", "add_or_update")
+                    .add_string_choice("Remove", "remove")
+            })
+            .create_option(|option| {
+                option
+                    .name("item")
+                    .description("The item that needs to be counted.")
+                    .required(true)
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Ender Pearls", "minecraft:ender_pearl")
+                    .add_string_choice("Blaze Rods", "minecraft:blaze_rod")
+                    .add_string_choice("Obsidian", "minecraft:obsidian")
+            })
+            .create_option(|option| {
+                option
+                    .name("split")
+                    .description("The split whose roles need the item.")
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Nether", Split::Nether.to_str())
+                    .add_string_choice("First Structure", Split::FirstStructure.to_str())
+                    .add_string_choice("Second Structure", Split::SecondStructure.to_str())
+                    .add_string_choice("Blind", Split::Blind.to_str())
+                    .add_string_choice("Eye Spy", Split::EyeSpy.to_str())
+                    .add_string_choice("End Enter", Split::EndEnter.to_str())
+                    .add_string_choice("Finish", Split::Finish.to_str())
+            })
+            .create_option(|option| {
+                option
+                    .name("role")
+                    .description("A single pace-role that needs the item, instead of a whole split.")
+                    .kind(CommandOptionType::Role)
+            })
+            .create_option(|option| {
+                option
+                    .name("count")
+                    .description("The minimum amount of the item.")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(0)
+            })
+        });
+        commands.create_application_command(|command| {
+            command
+            .name("reset_pings")
+            .description(
+                "Delete pings for a run once the runner resets (leave empty to keep them).",
+            )
+            .create_option(|option| {
+                option
+                    .name("delete_after")
+                    .description("Only delete pings that are older than this many minutes.")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(0)
+            })
+        });
+        commands.create_application_command(|command| {
+            command
+            .name("configure_channels")
+            .description(
+                "Choose the channel the bot uses for something (leave the channel empty to go back to the default).",
+            )
+            .create_option(|option| {
+                option
+                    .name("kind")
+                    .description("What the channel is used for.")
+                    .required(true)
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Pace", "pace")
+                    .add_string_choice("Finish", "finish")
+                    .add_string_choice("Leaderboard", "leaderboard")
+                    .add_string_choice("Config", "config")
+                    .add_string_choice("Audit", "audit")
+            })
+            .create_option(|option| {
+                option
+                    .name("channel")
+                    .description("The channel to use.")
+                    .kind(CommandOptionType::Channel)
+                    .channel_types(&[ChannelType::Text])
+            })
+        });
+        commands.create_application_command(|command| {
+            command
+            .name("routes")
+            .description(
+                "Send some splits, runners or run types to a different channel.",
             )
+            .create_option(|option| {
+                option
+                    .name("action")
+                    .description("Action to perform out of 'add', 'remove' or 'list'.")
+                    .required(true)
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Add", "add")
+                    .add_string_choice("Remove", "remove")
+                    .add_string_choice("List", "list")
+            })
+            .create_option(|option| {
+                option
+                    .name("channel")
+                    .description("The channel that matching runs are sent to.")
+                    .kind(CommandOptionType::Channel)
+                    .channel_types(&[ChannelType::Text])
+            })
+            .create_option(|option| {
+                option
+                    .name("split")
+                    .description("Only route this split.")
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Nether", Split::Nether.to_str())
+                    .add_string_choice("First Structure", Split::FirstStructure.to_str())
+                    .add_string_choice("Second Structure", Split::SecondStructure.to_str())
+                    .add_string_choice("Blind", Split::Blind.to_str())
+                    .add_string_choice("Eye Spy", Split::EyeSpy.to_str())
+                    .add_string_choice("End Enter", Split::EndEnter.to_str())
+                    .add_string_choice("Finish", Split::Finish.to_str())
+            })
+            .create_option(|option| {
+                option
+                    .name("runner")
+                    .description("Only route this runner.")
+                    .kind(CommandOptionType::String)
+            })
+            .create_option(|option| {
+                option
+                    .name("run_type")
+                    .description("Only route this kind of run.")
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Bastionless", "bastionless")
+                    .add_string_choice("Modern", "modern")
+            })
+            .create_option(|option| {
+                option
+                    .name("id")
+                    .description("The id of the route to remove.")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(1)
+            })
+        });
+        commands.create_application_command(|command| {
+            command
+            .name("subscribe")
+            .description(
+                "Get a DM when a runner gets a split under a time.",
+            )
+            .create_option(|option| {
+                option
+                    .name("action")
+                    .description("Action to perform out of 'add', 'remove' or 'list'.")
+                    .required(true)
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Add", "add")
+                    .add_string_choice("Remove", "remove")
+                    .add_string_choice("List", "list")
+            })
+            .create_option(|option| {
+                option
+                    .name("runner")
+                    .description("In-game name of the runner.")
+                    .kind(CommandOptionType::String)
+            })
+            .create_option(|option| {
+                option
+                    .name("split")
+                    .description("The split to get a DM for.")
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Nether", Split::Nether.to_str())
+                    .add_string_choice("First Structure", Split::FirstStructure.to_str())
+                    .add_string_choice("Second Structure", Split::SecondStructure.to_str())
+                    .add_string_choice("Blind", Split::Blind.to_str())
+                    .add_string_choice("Eye Spy", Split::EyeSpy.to_str())
+                    .add_string_choice("End Enter", Split::EndEnter.to_str())
+                    .add_string_choice("Finish", Split::Finish.to_str())
+            })
+            .create_option(|option| {
+                option
+                    .name("time")
+                    .description("Only DM for splits under this time (m:ss or minutes).")
+                    .kind(CommandOptionType::String)
+            })
+            .create_option(|option| {
+                option
+                    .name("id")
+                    .description("The id of the subscription to remove.")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(1)
+            })
+        });
+        commands.create_application_command(|command| {
+            command
+            .name("embed_template")
+            .description(
+                "Customize the embeds of pace and finish messages.",
+            )
+            .create_option(|option| {
+                option
+                    .name("action")
+                    .description("Action to perform out of 'set', 'reset' or 'preview'.")
+                    .required(true)
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Set", "set")
+                    .add_string_choice("Reset", "reset")
+                    .add_string_choice("Preview", "preview")
+            })
+            .create_option(|option| {
+                option
+                    .name("template")
+                    .description("The template as JSON (see the README for the format).")
+                    .kind(CommandOptionType::String)
+            })
+        });
+        commands.create_application_command(|command| {
+            command
+            .name("leaderboard_period")
+            .description(
+                "Show a weekly, monthly or seasonal leaderboard next to the all-time leaderboard.",
+            )
+            .create_option(|option| {
+                option
+                    .name("period")
+                    .description("The period of the leaderboard.")
+                    .required(true)
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("All-time only", "all_time")
+                    .add_string_choice("Weekly", "weekly")
+                    .add_string_choice("Monthly", "monthly")
+                    .add_string_choice("Season", "season")
+            })
+            .create_option(|option| {
+                option
+                    .name("start")
+                    .description("First day of the season (YYYY-MM-DD).")
+                    .kind(CommandOptionType::String)
+            })
+            .create_option(|option| {
+                option
+                    .name("end")
+                    .description("Last day of the season (YYYY-MM-DD).")
+                    .kind(CommandOptionType::String)
+            })
+        });
+        commands.create_application_command(|command| {
+            command
+            .name("leaderboard")
+            .description(
+                "View and correct the runner leaderboard.",
+            )
+            .create_option(|option| {
+                option
+                    .name("action")
+                    .description("Action to perform out of 'show', 'remove', 'set' or 'reset'.")
+                    .required(true)
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Show", "show")
+                    .add_string_choice("Remove", "remove")
+                    .add_string_choice("Set", "set")
+                    .add_string_choice("Reset", "reset")
+            })
+            .create_option(|option| {
+                option
+                    .name("runner")
+                    .description("In-game name of the runner.")
+                    .kind(CommandOptionType::String)
+            })
+            .create_option(|option| {
+                option
+                    .name("split")
+                    .description("The split to remove or set (removes all splits if not given).")
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Nether", Split::Nether.to_str())
+                    .add_string_choice("First Structure", Split::FirstStructure.to_str())
+                    .add_string_choice("Second Structure", Split::SecondStructure.to_str())
+                    .add_string_choice("Blind", Split::Blind.to_str())
+                    .add_string_choice("Eye Spy", Split::EyeSpy.to_str())
+                    .add_string_choice("End Enter", Split::EndEnter.to_str())
+                    .add_string_choice("Finish", Split::Finish.to_str())
+            })
+            .create_option(|option| {
+                option
+                    .name("time")
+                    .description("The new best time for 'set' (m:ss or minutes).")
+                    .kind(CommandOptionType::String)
+            })
+            .create_option(|option| {
+                option
+                    .name("date")
+                    .description("Day of the run for 'set' (YYYY-MM-DD, defaults to the day of the run it corrects).")
+                    .kind(CommandOptionType::String)
+            })
+        });
+        commands.create_application_command(|command| {
+            command
+            .name("rules")
+            .description(
+                "Ping a role when a run matches a condition, e.g. 'blind < 6:00 and bastionless -> @role'.",
+            )
+            .create_option(|option| {
+                option
+                    .name("action")
+                    .description("Action to perform out of 'add', 'remove' or 'list'.")
+                    .required(true)
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Add", "add")
+                    .add_string_choice("Remove", "remove")
+                    .add_string_choice("List", "list")
+            })
+            .create_option(|option| {
+                option
+                    .name("rule")
+                    .description("The rule to add, in the form '<condition> -> @role'.")
+                    .kind(CommandOptionType::String)
+            })
+            .create_option(|option| {
+                option
+                    .name("id")
+                    .description("The id of the rule to remove.")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(1)
+            })
         });
         commands.create_application_command(|command| {
             command
@@ -146,11 +491,13 @@ pub async fn setup_default_commands(ctx: &Context, guild_id: GuildId) {
                     .description("The name of the split.")
                     .kind(CommandOptionType::String)
                     .required(true)
+                    .add_string_choice("Nether", "nether")
                     .add_string_choice("First Structure", "first_structure")
                     .add_string_choice("Second Structure", "second_structure")
                     .add_string_choice("Blind", "blind")
                     .add_string_choice("Eye Spy", "eye_spy")
                     .add_string_choice("End Enter", "end_enter")
+                    .add_string_choice("Finish", "finish")
             })
             .create_option(|option| {
                 option
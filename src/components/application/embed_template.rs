@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use serenity::{
+    builder::{CreateEmbed, CreateEmbedAuthor},
+    client::Context,
+    model::{id::GuildId, prelude::application_command::ApplicationCommandInteraction},
+};
+
+use crate::{
+    cache::{embed_template::EmbedTemplate, split::Split, CacheManager},
+    db::consts::EMBED_TEMPLATE_SETTING,
+    handler::ArcMutex,
+    Result,
+};
+
+fn preview_embed(
+    title: String,
+    fields: Vec<(String, String, bool)>,
+    colour: Option<u32>,
+) -> CreateEmbed {
+    let mut author = CreateEmbedAuthor::default();
+    author.name("Runner");
+    let mut embed = CreateEmbed::default();
+    embed.set_author(author);
+    embed.field(title, "", true);
+    for (name, value, inline) in fields {
+        embed.field(name, value, inline);
+    }
+    if let Some(colour) = colour {
+        embed.colour(colour);
+    }
+    embed
+}
+
+// Renders the template for a made up bastionless blind and completion.
+fn preview_embeds(template: &EmbedTemplate) -> Vec<CreateEmbed> {
+    let sample_values = |split: &str, igt: &str, rta: &str| {
+        HashMap::from([
+            ("runner", "Runner".to_string()),
+            ("split", split.to_string()),
+            ("igt", igt.to_string()),
+            ("rta", rta.to_string()),
+            ("items", "Blaze Rods 7  Ender Pearls 12".to_string()),
+            (
+                "stats_link",
+                "https://paceman.gg/stats/run/preview".to_string(),
+            ),
+            ("live", ":red_circle:".to_string()),
+            ("run_type", "Bastionless".to_string()),
+            ("bastionless", "Yes".to_string()),
+            ("timestamp", "1700000000".to_string()),
+        ])
+    };
+    let pace_values = sample_values("First Portal", "5:30", "6:12");
+    let finish_values = sample_values("Finish", "9:45", "10:31");
+    vec![
+        preview_embed(
+            EmbedTemplate::render(&template.pace_title, &pace_values),
+            EmbedTemplate::render_fields(&template.pace_fields, &pace_values),
+            template.colour(&Split::Blind),
+        ),
+        preview_embed(
+            EmbedTemplate::render(&template.finish_title, &finish_values),
+            EmbedTemplate::render_fields(&template.finish_fields, &finish_values),
+            template.colour(&Split::Finish),
+        ),
+    ]
+}
+
+pub async fn embed_template(
+    ctx: &Context,
+    guild_id: GuildId,
+    command: &ApplicationCommandInteraction,
+    cache_manager: ArcMutex<CacheManager>,
+) -> Result<()> {
+    command.defer_ephemeral(&ctx).await?;
+    let mut action = String::new();
+    let mut json = None;
+    for option in command.data.options.iter() {
+        let value = match option
+            .value
+            .to_owned()
+            .and_then(|value| value.as_str().map(str::to_owned))
+        {
+            Some(value) => value,
+            None => {
+                return Err(format!(
+                    "EmbedTemplateError: get string value for option: '{}'.",
+                    option.name
+                )
+                .into())
+            }
+        };
+        match option.name.as_str() {
+            "action" => action = value,
+            "template" => json = Some(value),
+            _ => return Err(format!("Unrecognized command option: '{}'", option.name).into()),
+        };
+    }
+
+    // Templates are validated before they are saved or previewed.
+    let template = match json
+        .as_deref()
+        .map(|json| EmbedTemplate::from_json(json).map_err(|err| err.to_string()))
+    {
+        Some(Ok(template)) => Some(template),
+        Some(Err(err)) => {
+            let response_content = format!("Invalid template: {}", err);
+            command
+                .edit_original_interaction_response(&ctx.http, |m| {
+                    m.content(response_content.to_string())
+                })
+                .await?;
+            return Err(response_content.into());
+        }
+        None => None,
+    };
+    let mut locked_cache_manager = cache_manager.lock().await;
+    let response_content = match (action.as_str(), &template) {
+        ("set", Some(template)) => {
+            locked_cache_manager.db.set_setting(
+                guild_id,
+                EMBED_TEMPLATE_SETTING,
+                template.to_json().as_str(),
+            )?;
+            "Updated the embed template!"
+        }
+        ("set", None) => {
+            drop(locked_cache_manager);
+            let response_content =
+                "EmbedTemplateError: Parameter 'template' is undefined for 'set'.";
+            command
+                .edit_original_interaction_response(&ctx.http, |m| m.content(response_content))
+                .await?;
+            return Err(response_content.into());
+        }
+        ("reset", _) => {
+            locked_cache_manager
+                .db
+                .remove_setting(guild_id, EMBED_TEMPLATE_SETTING)?;
+            "Reset the embed template to the default!"
+        }
+        _ => "Preview of the embed template:",
+    };
+    if action != "preview" {
+        locked_cache_manager.add_or_update_guild(ctx, guild_id)?;
+    }
+    // Previews show the given template if there is one, and the saved one otherwise.
+    let template = match template {
+        Some(template) if action == "preview" => template,
+        _ => match locked_cache_manager.cache.get(&guild_id) {
+            Some(guild_data) => guild_data.embed_template.clone(),
+            None => EmbedTemplate::default(),
+        },
+    };
+    drop(locked_cache_manager);
+    let content = format!("{}\n```json\n{}\n```", response_content, template.to_json());
+    command
+        .edit_original_interaction_response(&ctx.http, |m| {
+            m.content(content).add_embeds(preview_embeds(&template))
+        })
+        .await?;
+    Ok(())
+}
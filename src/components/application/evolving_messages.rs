@@ -0,0 +1,53 @@
+use serenity::{
+    client::Context,
+    model::{id::GuildId, prelude::application_command::ApplicationCommandInteraction},
+};
+
+use crate::{
+    cache::CacheManager, db::consts::EVOLVING_MESSAGES_SETTING, handler::ArcMutex, Result,
+};
+
+pub async fn evolving_messages(
+    ctx: &Context,
+    guild_id: GuildId,
+    command: &ApplicationCommandInteraction,
+    cache_manager: ArcMutex<CacheManager>,
+) -> Result<()> {
+    command.defer_ephemeral(&ctx).await?;
+    let enabled = match command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == "enabled")
+        .and_then(|option| option.value.to_owned())
+    {
+        Some(value) => match value.as_bool() {
+            Some(enabled) => enabled,
+            None => {
+                return Err(
+                    String::from("EvolvingMessagesError: parse bool for enabled option.").into(),
+                )
+            }
+        },
+        None => {
+            return Err(String::from("EvolvingMessagesError: get value for enabled option.").into())
+        }
+    };
+    let mut locked_cache_manager = cache_manager.lock().await;
+    locked_cache_manager.db.set_setting(
+        guild_id,
+        EVOLVING_MESSAGES_SETTING,
+        enabled.to_string().as_str(),
+    )?;
+    locked_cache_manager.add_or_update_guild(ctx, guild_id)?;
+    drop(locked_cache_manager);
+    let response_content = if enabled {
+        "Each run will now be kept in a single message that is edited as new splits come in!"
+    } else {
+        "Each split will now be sent as a separate message!"
+    };
+    command
+        .edit_original_interaction_response(&ctx.http, |m| m.content(response_content))
+        .await?;
+    Ok(())
+}
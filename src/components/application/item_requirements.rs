@@ -0,0 +1,156 @@
+use serenity::{
+    client::Context,
+    model::{
+        id::{GuildId, RoleId},
+        prelude::application_command::ApplicationCommandInteraction,
+    },
+};
+
+use crate::{
+    cache::{
+        item_requirement::{ItemRequirement, RequirementTarget},
+        split::Split,
+        CacheManager,
+    },
+    handler::ArcMutex,
+    Result,
+};
+
+pub async fn item_requirements(
+    ctx: &Context,
+    guild_id: GuildId,
+    command: &ApplicationCommandInteraction,
+    cache_manager: ArcMutex<CacheManager>,
+) -> Result<()> {
+    command.defer_ephemeral(&ctx).await?;
+    let mut action = String::new();
+    let mut item = String::new();
+    let mut target = None;
+    let mut count = None;
+    for option in command.data.options.iter() {
+        let value = match option.value.to_owned() {
+            Some(value) => value,
+            None => {
+                return Err(format!(
+                    "ItemRequirementsError: get value for option: '{}'.",
+                    option.name
+                )
+                .into())
+            }
+        };
+        match option.name.as_str() {
+            "action" | "item" | "split" | "role" => {
+                let value = match value.as_str() {
+                    Some(str) => str.to_owned(),
+                    None => {
+                        return Err(format!(
+                            "ItemRequirementsError: parse string for option: '{}'.",
+                            option.name
+                        )
+                        .into())
+                    }
+                };
+                match option.name.as_str() {
+                    "action" => action = value,
+                    "item" => item = value,
+                    "split" => {
+                        target = match Split::from_str(value.as_str()) {
+                            Some(split) => Some(RequirementTarget::Split(split)),
+                            None => {
+                                return Err(format!(
+                                    "ItemRequirementsError: Unrecognized split: '{}'.",
+                                    value
+                                )
+                                .into())
+                            }
+                        }
+                    }
+                    _ => target = Some(RequirementTarget::Role(RoleId(value.parse::<u64>()?))),
+                };
+            }
+            "count" => {
+                count = match value.as_u64() {
+                    Some(int) => Some(int as u32),
+                    None => {
+                        return Err(String::from(
+                            "ItemRequirementsError: parse u64 for count option.",
+                        )
+                        .into())
+                    }
+                }
+            }
+            _ => return Err(format!("Unrecognized command option: '{}'", option.name).into()),
+        };
+    }
+
+    let target = match target {
+        Some(target) => target,
+        None => {
+            let response_content = "ItemRequirementsError: Either a 'split' or a 'role' is needed.";
+            command
+                .edit_original_interaction_response(&ctx.http, |m| m.content(response_content))
+                .await?;
+            return Err(response_content.into());
+        }
+    };
+    let requirement = ItemRequirement {
+        target,
+        item: ItemRequirement::item_from_id(item.as_str()),
+        min_count: count.unwrap_or_default(),
+    };
+
+    let mut locked_cache_manager = cache_manager.lock().await;
+    if action == "remove" {
+        if !locked_cache_manager
+            .db
+            .remove_item_requirement(guild_id, &requirement)?
+        {
+            let response_content = format!(
+                "ItemRequirementsError: No requirement for item: '{}' to remove in guild id: {}",
+                item, guild_id
+            );
+            drop(locked_cache_manager);
+            command
+                .edit_original_interaction_response(&ctx.http, |m| {
+                    m.content(response_content.to_string())
+                })
+                .await?;
+            return Err(response_content.into());
+        }
+    } else {
+        if count.is_none() {
+            let response_content =
+                "ItemRequirementsError: Parameter 'count' is undefined for 'add_or_update'.";
+            drop(locked_cache_manager);
+            command
+                .edit_original_interaction_response(&ctx.http, |m| m.content(response_content))
+                .await?;
+            return Err(response_content.into());
+        }
+        locked_cache_manager
+            .db
+            .upsert_item_requirement(guild_id, &requirement)?;
+    }
+    locked_cache_manager.add_or_update_guild(ctx, guild_id)?;
+    let requirements = match locked_cache_manager.cache.get(&guild_id) {
+        Some(guild_data) => guild_data
+            .item_requirements
+            .iter()
+            .map(|requirement| format!("- {}", requirement))
+            .collect::<Vec<_>>(),
+        None => vec![],
+    };
+    drop(locked_cache_manager);
+    let response_content = if requirements.is_empty() {
+        "Updated item requirements! There are no item requirements anymore.".to_string()
+    } else {
+        format!(
+            "Updated item requirements! Current requirements:\n{}",
+            requirements.join("\n")
+        )
+    };
+    command
+        .edit_original_interaction_response(&ctx.http, |m| m.content(response_content))
+        .await?;
+    Ok(())
+}
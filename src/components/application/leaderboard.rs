@@ -0,0 +1,233 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serenity::{
+    client::Context,
+    model::{id::GuildId, prelude::application_command::ApplicationCommandInteraction},
+};
+
+use crate::{
+    cache::{
+        consts::LEADERBOARD_PAGE_LIMIT, leaderboard::LeaderboardEntry, split::Split, CacheManager,
+    },
+    handler::ArcMutex,
+    utils::{
+        format_time::format_time, parse_date::parse_date, parse_time::parse_time,
+        render_leaderboard::render_leaderboard, update_leaderboard::refresh_leaderboard,
+    },
+    Result,
+};
+
+#[derive(Default)]
+struct LeaderboardOptions {
+    action: String,
+    runner: Option<String>,
+    split: Option<Split>,
+    time: Option<u64>,
+    date: Option<i64>,
+}
+
+// Invalid options are reported back to the user, so the errors are plain strings.
+fn get_options(
+    command: &ApplicationCommandInteraction,
+) -> std::result::Result<LeaderboardOptions, String> {
+    let mut options = LeaderboardOptions::default();
+    for option in command.data.options.iter() {
+        let value = match option
+            .value
+            .as_ref()
+            .and_then(|value| value.as_str())
+            .map(|value| value.trim().to_owned())
+        {
+            Some(value) => value,
+            None => {
+                return Err(format!(
+                    "LeaderboardError: get string value for option: '{}'.",
+                    option.name
+                ))
+            }
+        };
+        match option.name.as_str() {
+            "action" => options.action = value,
+            "runner" => options.runner = Some(value),
+            "split" => {
+                options.split = match Split::from_str(value.as_str()) {
+                    Some(split) => Some(split),
+                    None => {
+                        return Err(format!(
+                            "LeaderboardError: Unrecognized split: '{}'.",
+                            value
+                        ))
+                    }
+                }
+            }
+            "time" => match parse_time(value.as_str()) {
+                Ok(time) => options.time = Some(time),
+                Err(err) => return Err(format!("LeaderboardError: Invalid time: {}", err)),
+            },
+            "date" => match parse_date(value.as_str()) {
+                Ok(date) => options.date = Some(date),
+                Err(err) => return Err(format!("LeaderboardError: Invalid date: {}", err)),
+            },
+            _ => return Err(format!("Unrecognized command option: '{}'", option.name)),
+        };
+    }
+    Ok(options)
+}
+
+pub async fn leaderboard(
+    ctx: &Context,
+    guild_id: GuildId,
+    command: &ApplicationCommandInteraction,
+    cache_manager: ArcMutex<CacheManager>,
+) -> Result<()> {
+    command.defer_ephemeral(&ctx).await?;
+    let LeaderboardOptions {
+        action,
+        runner,
+        split,
+        time,
+        date,
+    } = match get_options(command) {
+        Ok(options) => options,
+        Err(response_content) => {
+            command
+                .edit_original_interaction_response(&ctx.http, |m| {
+                    m.content(response_content.to_string())
+                })
+                .await?;
+            return Err(response_content.into());
+        }
+    };
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+
+    let locked_cache_manager = cache_manager.lock().await;
+    let db = &locked_cache_manager.db;
+    let found_runner = match &runner {
+        Some(runner) => db.find_leaderboard_runner(guild_id, runner)?,
+        None => None,
+    };
+    let response_content = match (action.as_str(), runner, found_runner) {
+        ("show", None, _) => {
+            let period = locked_cache_manager
+                .cache
+                .get(&guild_id)
+                .and_then(|guild_data| guild_data.leaderboard_period.as_ref());
+            let pages = render_leaderboard(db, guild_id, period, now)?;
+            // Only the first page fits into the response, and it is cut short so the
+            // note about the other pages fits as well.
+            let mut content = pages.first().cloned().unwrap_or_default();
+            if pages.len() > 1 {
+                let note = "\n\n...and more in the leaderboard channel.";
+                while content.len() + note.len() > LEADERBOARD_PAGE_LIMIT {
+                    match content.rfind('\n') {
+                        Some(idx) => content.truncate(idx),
+                        None => content.clear(),
+                    };
+                }
+                content.push_str(note);
+            }
+            Ok(content)
+        }
+        ("reset", _, _) => {
+            let removed = db.reset_leaderboard(guild_id)?;
+            Ok(format!(
+                "Removed all {} runs from the leaderboard!",
+                removed
+            ))
+        }
+        (_, None, _) => Err(format!(
+            "LeaderboardError: Parameter 'runner' is undefined for '{}'.",
+            action
+        )),
+        (_, Some(runner), None) => Err(format!(
+            "LeaderboardError: No runner with name: '{}' on the leaderboard.",
+            runner
+        )),
+        ("show", _, Some((uuid, _))) => {
+            match db.get_leaderboard(guild_id, None)?.render_runner(&uuid) {
+                Some(content) => Ok(content),
+                None => Err("LeaderboardError: get runs of runner.".to_string()),
+            }
+        }
+        ("remove", _, Some((uuid, name))) => {
+            let removed = db.remove_leaderboard_runs(guild_id, &uuid, split.as_ref())?;
+            let split = match &split {
+                Some(split) => format!("{} runs", split.alt_desc()),
+                None => "runs".to_string(),
+            };
+            Ok(format!(
+                "Removed {} {} of {} from the leaderboard!",
+                removed, split, name
+            ))
+        }
+        ("set", _, Some((uuid, name))) => match (split, time) {
+            (Some(split), Some(time)) => {
+                // Without a date, the corrected run keeps the day of the run it replaces
+                // as the best, so it stays in the period it was run in.
+                let date = match date {
+                    Some(date) => date,
+                    None => db
+                        .get_leaderboard(guild_id, None)?
+                        .bests
+                        .iter()
+                        .find(|entry| entry.uuid == uuid && entry.split == split)
+                        .map(|entry| entry.date)
+                        .unwrap_or(now),
+                };
+                let removed = db.set_leaderboard_best(
+                    guild_id,
+                    &LeaderboardEntry {
+                        uuid,
+                        runner: name.to_owned(),
+                        split: split.to_owned(),
+                        time,
+                        world_id: None,
+                        date,
+                    },
+                )?;
+                Ok(format!(
+                    "Set the best {} of {} to {} and removed {} faster runs!",
+                    split.alt_desc(),
+                    name,
+                    format_time(time),
+                    removed
+                ))
+            }
+            _ => Err(
+                "LeaderboardError: Parameters 'split' and 'time' are needed for 'set'.".to_string(),
+            ),
+        },
+        _ => Err(format!(
+            "LeaderboardError: Unrecognized action: '{}'.",
+            action
+        )),
+    };
+    let mut response_content = match response_content {
+        Ok(content) => content,
+        Err(response_content) => {
+            drop(locked_cache_manager);
+            command
+                .edit_original_interaction_response(&ctx.http, |m| {
+                    m.content(response_content.to_string())
+                })
+                .await?;
+            return Err(response_content.into());
+        }
+    };
+    drop(locked_cache_manager);
+    if action != "show" {
+        if let Err(err) = refresh_leaderboard(ctx, guild_id, &cache_manager)
+            .await
+            .map_err(|err| err.to_string())
+        {
+            response_content = format!(
+                "{}\nCould not update the leaderboard: {}",
+                response_content, err
+            );
+        }
+    }
+    command
+        .edit_original_interaction_response(&ctx.http, |m| m.content(response_content))
+        .await?;
+    Ok(())
+}
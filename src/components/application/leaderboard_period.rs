@@ -0,0 +1,121 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serenity::{
+    client::Context,
+    model::{id::GuildId, prelude::application_command::ApplicationCommandInteraction},
+};
+
+use crate::{
+    cache::{leaderboard::LeaderboardPeriod, CacheManager},
+    db::consts::{LEADERBOARD_PERIOD_SETTING, LEADERBOARD_ROLLOVER_SETTING},
+    handler::ArcMutex,
+    utils::update_leaderboard::refresh_leaderboard,
+    Result,
+};
+
+pub async fn leaderboard_period(
+    ctx: &Context,
+    guild_id: GuildId,
+    command: &ApplicationCommandInteraction,
+    cache_manager: ArcMutex<CacheManager>,
+) -> Result<()> {
+    command.defer_ephemeral(&ctx).await?;
+    let mut period = None;
+    let mut start = None;
+    let mut end = None;
+    for option in command.data.options.iter() {
+        let value = match option
+            .value
+            .to_owned()
+            .and_then(|value| value.as_str().map(|value| value.trim().to_string()))
+        {
+            Some(value) => value,
+            None => {
+                return Err(format!(
+                    "LeaderboardPeriodError: parse string for option: '{}'.",
+                    option.name
+                )
+                .into())
+            }
+        };
+        match option.name.as_str() {
+            "period" => period = Some(value),
+            "start" => start = Some(value),
+            "end" => end = Some(value),
+            _ => return Err(format!("Unrecognized command option: '{}'", option.name).into()),
+        };
+    }
+    let period = match period.as_deref() {
+        Some("all_time") => Ok(None),
+        Some("weekly") => Ok(Some(LeaderboardPeriod::Weekly)),
+        Some("monthly") => Ok(Some(LeaderboardPeriod::Monthly)),
+        Some("season") => match (start, end) {
+            (Some(start), Some(end)) => match LeaderboardPeriod::season(&start, &end) {
+                Some(season) => Ok(Some(season)),
+                None => Err(format!(
+                    "Could not parse a season from '{}' to '{}'. Dates are written as YYYY-MM-DD and the season has to end after it starts.",
+                    start, end
+                )),
+            },
+            _ => Err("A season needs both a start and an end date.".to_string()),
+        },
+        _ => return Err("LeaderboardPeriodError: get value for period option.".into()),
+    };
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+
+    let mut locked_cache_manager = cache_manager.lock().await;
+    let response_content = match period {
+        Ok(Some(period)) => {
+            locked_cache_manager.db.set_setting(
+                guild_id,
+                LEADERBOARD_PERIOD_SETTING,
+                period.to_setting().as_str(),
+            )?;
+            // Periods that ended before this point are not archived anymore.
+            locked_cache_manager.db.set_setting(
+                guild_id,
+                LEADERBOARD_ROLLOVER_SETTING,
+                now.to_string().as_str(),
+            )?;
+            Ok(format!(
+                "The leaderboard now shows a {} board next to the all-time board!",
+                period.name().to_lowercase()
+            ))
+        }
+        Ok(None) => {
+            locked_cache_manager
+                .db
+                .remove_setting(guild_id, LEADERBOARD_PERIOD_SETTING)?;
+            Ok("The leaderboard now only shows the all-time board!".to_string())
+        }
+        Err(err) => Err(err),
+    };
+    let update_result = locked_cache_manager
+        .add_or_update_guild(ctx, guild_id)
+        .map_err(|err| err.to_string());
+    drop(locked_cache_manager);
+    let update_leaderboard = response_content.is_ok() && update_result.is_ok();
+    let mut response_content = match (response_content, update_result) {
+        (Ok(content), Ok(_)) => content,
+        (Ok(content), Err(err)) => format!(
+            "{}\nThe configuration is not valid yet, so the leaderboard was not updated: {}",
+            content, err
+        ),
+        (Err(err), _) => err,
+    };
+    if update_leaderboard {
+        if let Err(err) = refresh_leaderboard(ctx, guild_id, &cache_manager)
+            .await
+            .map_err(|err| err.to_string())
+        {
+            response_content = format!(
+                "{}\nCould not update the leaderboard: {}",
+                response_content, err
+            );
+        }
+    }
+    command
+        .edit_original_interaction_response(&ctx.http, |m| m.content(response_content))
+        .await?;
+    Ok(())
+}
@@ -3,26 +3,27 @@ use serenity::{
     model::{id::GuildId, prelude::application_command::ApplicationCommandInteraction},
 };
 
-use crate::Result;
+use crate::{
+    cache::{channel_kind::ChannelKind, CacheManager},
+    db::consts::CONFIG_IMPORTED_SETTING,
+    handler::ArcMutex,
+    utils::import_config_message::import_config_message,
+    Result,
+};
 
 pub async fn migrate(
     ctx: &Context,
     guild: GuildId,
     command: &ApplicationCommandInteraction,
+    cache_manager: ArcMutex<CacheManager>,
 ) -> Result<()> {
     command.defer_ephemeral(&ctx).await?;
-    let channels = match ctx.cache.guild_channels(guild) {
-        Some(channels) => channels,
-        None => return Err(format!("MigrateError: get channels for guild id: {}", guild).into()),
-    };
-    let runner_names_channel = match channels
-        .iter()
-        .find(|c| c.name == "pacemanbot-runner-names")
-    {
-        Some(channel) => channel,
+    let config_channel = ChannelKind::Config.resolve(ctx, guild, &cache_manager.lock().await.db)?;
+    let runner_names_channel = match config_channel {
+        Some(channel_id) => channel_id,
         None => {
             let response_content = format!(
-                "MigrateError: find channel name: pacemanbot-runner-name in guild id: {}",
+                "MigrateError: find #pacemanbot-runner-names or a configured config channel in guild id: {}",
                 guild
             );
             command
@@ -33,51 +34,37 @@ pub async fn migrate(
             return Err(response_content.into());
         }
     };
-    let old_config_messages = match runner_names_channel
-        .messages(&ctx.http, |m| m.limit(1))
+    let imported = match import_config_message(ctx, guild, runner_names_channel, &cache_manager)
         .await
-    {
-        Ok(messages) => messages,
-        Err(err) => {
-            return Err(format!(
-                "MigrateError: get messages in #pacemanbot-runner-names in guild id: {} due to: {}",
+        .map_err(|err| {
+            format!(
+                "MigrateError: import first message in #pacemanbot-runner-names in guild id: {} due to: {}",
                 guild, err
             )
-            .into())
-        }
-    };
-    let old_config_message = match old_config_messages.last() {
-        Some(message) => message,
-        None => {
-            return Err(format!(
-                "MigrateError: get first message in #pacemanbot-runner-names in guild id: {}",
-                guild
-            )
-            .into())
+        }) {
+        Ok(imported) => imported,
+        Err(response_content) => {
+            command
+                .edit_original_interaction_response(&ctx.http, |m| {
+                    m.content(response_content.to_owned())
+                })
+                .await?;
+            return Err(response_content.into());
         }
     };
-    let is_code_block = old_config_message.content.starts_with("```")
-        && old_config_message.content.ends_with("```");
-    if old_config_message.author.bot && is_code_block {
-        let response_content = format!(
-            "MigrateError: Migration has already been performed for guild id: {}.",
-            guild
-        );
-        return Err(response_content.into());
+    {
+        let mut locked_cache_manager = cache_manager.lock().await;
+        locked_cache_manager
+            .db
+            .set_setting(guild, CONFIG_IMPORTED_SETTING, "true")?;
+        locked_cache_manager.add_or_update_guild(ctx, guild)?;
     }
-    runner_names_channel
-        .send_message(&ctx.http, |m| {
-            m.content(format!(
-                "```\n{}\n```",
-                old_config_message.content.to_string()
-            ))
-        })
-        .await?;
     command
         .edit_original_interaction_response(&ctx.http, |m| {
-            m.content(String::from(
-                "Migrated old config from first message in #pacemanbot-runner-names! \
-                    You can now delete the original first message.",
+            m.content(format!(
+                "Imported {} runners from the first message in #pacemanbot-runner-names! \
+                    Use `/whitelist` to make further changes, the message is not read anymore.",
+                imported
             ))
         })
         .await?;
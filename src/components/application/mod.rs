@@ -1,9 +1,19 @@
+pub mod configure_channels;
 pub mod default_commands;
+pub mod embed_template;
+pub mod evolving_messages;
+pub mod item_requirements;
+pub mod leaderboard;
+pub mod leaderboard_period;
 pub mod migrate;
+pub mod reset_pings;
+pub mod routes;
+pub mod rules;
 pub mod send_role_selection_message;
 pub mod setup_default_roles;
 pub mod setup_pb_roles;
 pub mod setup_pings;
 pub mod setup_roles;
+pub mod subscribe;
 pub mod validate_config;
 pub mod whitelist;
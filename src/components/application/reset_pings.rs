@@ -0,0 +1,60 @@
+use serenity::{
+    client::Context,
+    model::{id::GuildId, prelude::application_command::ApplicationCommandInteraction},
+};
+
+use crate::{
+    cache::CacheManager, db::consts::RESET_PING_WINDOW_SETTING, handler::ArcMutex, Result,
+};
+
+pub async fn reset_pings(
+    ctx: &Context,
+    guild_id: GuildId,
+    command: &ApplicationCommandInteraction,
+    cache_manager: ArcMutex<CacheManager>,
+) -> Result<()> {
+    command.defer_ephemeral(&ctx).await?;
+    let delete_after = match command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == "delete_after")
+        .and_then(|option| option.value.to_owned())
+    {
+        Some(value) => match value.as_u64() {
+            Some(mins) => Some(mins),
+            None => {
+                return Err(
+                    String::from("ResetPingsError: parse u64 for delete after option.").into(),
+                )
+            }
+        },
+        None => None,
+    };
+    let mut locked_cache_manager = cache_manager.lock().await;
+    let response_content = match delete_after {
+        Some(mins) => {
+            locked_cache_manager.db.set_setting(
+                guild_id,
+                RESET_PING_WINDOW_SETTING,
+                mins.to_string().as_str(),
+            )?;
+            format!(
+                "Pings older than {} minutes will be deleted when the runner resets!",
+                mins
+            )
+        }
+        None => {
+            locked_cache_manager
+                .db
+                .remove_setting(guild_id, RESET_PING_WINDOW_SETTING)?;
+            "Pings will not be deleted when the runner resets anymore!".to_string()
+        }
+    };
+    locked_cache_manager.add_or_update_guild(ctx, guild_id)?;
+    drop(locked_cache_manager);
+    command
+        .edit_original_interaction_response(&ctx.http, |m| m.content(response_content))
+        .await?;
+    Ok(())
+}
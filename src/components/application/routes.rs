@@ -0,0 +1,144 @@
+use serenity::{
+    client::Context,
+    model::{
+        id::{ChannelId, GuildId},
+        prelude::application_command::ApplicationCommandInteraction,
+    },
+};
+
+use crate::{
+    cache::{route::Route, split::Split, CacheManager},
+    dispatcher::run_info::RunType,
+    handler::ArcMutex,
+    Result,
+};
+
+pub async fn routes(
+    ctx: &Context,
+    guild_id: GuildId,
+    command: &ApplicationCommandInteraction,
+    cache_manager: ArcMutex<CacheManager>,
+) -> Result<()> {
+    command.defer_ephemeral(&ctx).await?;
+    let mut action = String::new();
+    let mut route = Route {
+        id: 0,
+        channel_id: ChannelId(0),
+        split: None,
+        runner: None,
+        run_type: None,
+    };
+    let mut id = None;
+    for option in command.data.options.iter() {
+        let value = match option.value.to_owned() {
+            Some(value) => value,
+            None => {
+                return Err(format!("RoutesError: get value for option: '{}'.", option.name).into())
+            }
+        };
+        if option.name == "id" {
+            id = match value.as_i64() {
+                Some(int) => Some(int),
+                None => return Err("RoutesError: parse i64 for id option.".into()),
+            };
+            continue;
+        }
+        let value = match value.as_str() {
+            Some(str) => str.to_owned(),
+            None => {
+                return Err(
+                    format!("RoutesError: parse string for option: '{}'.", option.name).into(),
+                )
+            }
+        };
+        match option.name.as_str() {
+            "action" => action = value,
+            "channel" => route.channel_id = ChannelId(value.parse::<u64>()?),
+            "split" => {
+                route.split = match Split::from_str(value.as_str()) {
+                    Some(split) => Some(split),
+                    None => {
+                        return Err(format!("RoutesError: Unrecognized split: '{}'.", value).into())
+                    }
+                }
+            }
+            "runner" => route.runner = Some(value.trim().to_owned()),
+            "run_type" => {
+                route.run_type = match RunType::from_command_param(value.as_str()) {
+                    Some(run_type) => Some(run_type),
+                    None => {
+                        return Err(
+                            format!("RoutesError: Unrecognized run type: '{}'.", value).into()
+                        )
+                    }
+                }
+            }
+            _ => return Err(format!("Unrecognized command option: '{}'", option.name).into()),
+        };
+    }
+
+    let mut locked_cache_manager = cache_manager.lock().await;
+    let response_content = match action.as_str() {
+        "add" if route.channel_id == ChannelId(0) => {
+            Err("RoutesError: Parameter 'channel' is undefined for 'add'.".to_string())
+        }
+        // A route without criteria would catch every split.
+        "add" if route.split.is_none() && route.runner.is_none() && route.run_type.is_none() => {
+            Err(
+                "RoutesError: Either a 'split', a 'runner' or a 'run_type' is needed for 'add'."
+                    .to_string(),
+            )
+        }
+        "add" => {
+            let id = locked_cache_manager.db.add_route(guild_id, &route)?;
+            Ok(format!("Added route #{}!", id))
+        }
+        "remove" => match id {
+            Some(id) if locked_cache_manager.db.remove_route(guild_id, id)? => {
+                Ok(format!("Removed route #{}!", id))
+            }
+            Some(id) => Err(format!(
+                "RoutesError: No route with id: {} to remove in guild id: {}",
+                id, guild_id
+            )),
+            None => Err("RoutesError: Parameter 'id' is undefined for 'remove'.".to_string()),
+        },
+        _ => Ok(String::new()),
+    };
+    let response_content = match response_content {
+        Ok(content) => content,
+        Err(response_content) => {
+            drop(locked_cache_manager);
+            command
+                .edit_original_interaction_response(&ctx.http, |m| {
+                    m.content(response_content.to_string())
+                })
+                .await?;
+            return Err(response_content.into());
+        }
+    };
+    locked_cache_manager.add_or_update_guild(ctx, guild_id)?;
+    let routes = match locked_cache_manager.cache.get(&guild_id) {
+        Some(guild_data) => guild_data
+            .routes
+            .iter()
+            .map(|route| format!("- {}", route))
+            .collect::<Vec<_>>(),
+        None => vec![],
+    };
+    drop(locked_cache_manager);
+    let routes_content = if routes.is_empty() {
+        "There are no routes, everything goes to the pace channel.".to_string()
+    } else {
+        format!(
+            "Current routes (the first matching route is used):\n{}",
+            routes.join("\n")
+        )
+    };
+    command
+        .edit_original_interaction_response(&ctx.http, |m| {
+            m.content(format!("{}\n{}", response_content, routes_content).trim())
+        })
+        .await?;
+    Ok(())
+}
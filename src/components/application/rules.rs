@@ -0,0 +1,120 @@
+use serenity::{
+    client::Context,
+    model::{id::GuildId, prelude::application_command::ApplicationCommandInteraction},
+};
+
+use crate::{cache::CacheManager, handler::ArcMutex, rules::parser::parse_rule, Result};
+
+pub async fn rules(
+    ctx: &Context,
+    guild_id: GuildId,
+    command: &ApplicationCommandInteraction,
+    cache_manager: ArcMutex<CacheManager>,
+) -> Result<()> {
+    command.defer_ephemeral(&ctx).await?;
+    let mut action = String::new();
+    let mut source = None;
+    let mut id = None;
+    for option in command.data.options.iter() {
+        let value = match option.value.to_owned() {
+            Some(value) => value,
+            None => {
+                return Err(format!("RulesError: get value for option: '{}'.", option.name).into())
+            }
+        };
+        match option.name.as_str() {
+            "action" | "rule" => {
+                let value = match value.as_str() {
+                    Some(str) => str.to_owned(),
+                    None => {
+                        return Err(format!(
+                            "RulesError: parse string for option: '{}'.",
+                            option.name
+                        )
+                        .into())
+                    }
+                };
+                match option.name.as_str() {
+                    "action" => action = value,
+                    _ => source = Some(value),
+                };
+            }
+            "id" => {
+                id = match value.as_i64() {
+                    Some(int) => Some(int),
+                    None => return Err("RulesError: parse i64 for id option.".into()),
+                }
+            }
+            _ => return Err(format!("Unrecognized command option: '{}'", option.name).into()),
+        };
+    }
+
+    let mut locked_cache_manager = cache_manager.lock().await;
+    let response_content = match action.as_str() {
+        "add" => match source.as_deref().map(str::trim) {
+            // Rules are validated before they are saved, so a broken rule never reaches
+            // the dispatcher.
+            Some(source) => match parse_rule(source) {
+                Ok(rule)
+                    if ctx
+                        .cache
+                        .guild(guild_id)
+                        .is_some_and(|guild| guild.roles.contains_key(&rule.role_id)) =>
+                {
+                    let id = locked_cache_manager.db.add_rule(guild_id, source)?;
+                    Ok(format!("Added rule #{}!", id))
+                }
+                Ok(rule) => Err(format!(
+                    "RulesError: Role with id: {} does not exist in guild id: {}",
+                    rule.role_id, guild_id
+                )),
+                Err(err) => Err(format!("Invalid rule: {}", err)),
+            },
+            None => Err("RulesError: Parameter 'rule' is undefined for 'add'.".to_string()),
+        },
+        "remove" => match id {
+            Some(id) if locked_cache_manager.db.remove_rule(guild_id, id)? => {
+                Ok(format!("Removed rule #{}!", id))
+            }
+            Some(id) => Err(format!(
+                "RulesError: No rule with id: {} to remove in guild id: {}",
+                id, guild_id
+            )),
+            None => Err("RulesError: Parameter 'id' is undefined for 'remove'.".to_string()),
+        },
+        _ => Ok(String::new()),
+    };
+    let response_content = match response_content {
+        Ok(content) => content,
+        Err(response_content) => {
+            drop(locked_cache_manager);
+            command
+                .edit_original_interaction_response(&ctx.http, |m| {
+                    m.content(response_content.to_string())
+                })
+                .await?;
+            return Err(response_content.into());
+        }
+    };
+    locked_cache_manager.add_or_update_guild(ctx, guild_id)?;
+    let rules = match locked_cache_manager.cache.get(&guild_id) {
+        Some(guild_data) => guild_data
+            .rules
+            .iter()
+            .map(|guild_rule| format!("- #{}: `{}`", guild_rule.id, guild_rule.source))
+            .collect::<Vec<_>>(),
+        None => vec![],
+    };
+    drop(locked_cache_manager);
+    let rules_content = if rules.is_empty() {
+        "There are no rules.".to_string()
+    } else {
+        format!("Current rules:\n{}", rules.join("\n"))
+    };
+    command
+        .edit_original_interaction_response(&ctx.http, |m| {
+            m.content(format!("{}\n{}", response_content, rules_content).trim())
+        })
+        .await?;
+    Ok(())
+}
@@ -15,7 +15,7 @@ use serenity::{
 use crate::{
     cache::split::Split,
     utils::{
-        create_select_option::create_select_option,
+        consts::MAX_ACTION_ROWS, create_select_option::create_select_option,
         extract_split_from_pb_role_name::extract_split_from_pb_role_name,
         extract_split_from_role_name::extract_split_from_role_name,
         mins_secs_to_millis::mins_secs_to_millis,
@@ -51,7 +51,7 @@ pub async fn send_role_selection_message(
                     return Ordering::Equal;
                 }
             };
-            r1_order = mins_secs_to_millis((minutes, seconds));
+            r1_order = mins_secs_to_millis((minutes, seconds)).unwrap_or_default();
         }
         if r2.name.contains("PB") {
             r2_order = 0;
@@ -66,108 +66,77 @@ pub async fn send_role_selection_message(
                     return Ordering::Equal;
                 }
             };
-            r2_order = mins_secs_to_millis((minutes, seconds));
+            r2_order = mins_secs_to_millis((minutes, seconds)).unwrap_or_default();
         }
         r1_order.cmp(&r2_order)
     });
-    let mut select_bastion_role_action_row = CreateActionRow::default();
-    let mut select_fortress_role_action_row = CreateActionRow::default();
-    let mut select_blind_role_action_row = CreateActionRow::default();
-    let mut select_eye_spy_role_action_row = CreateActionRow::default();
-    let mut select_end_enter_role_action_row = CreateActionRow::default();
-
-    let send_bastion_picker = roles.iter().any(|role| {
-        if role.name.contains("PB") {
-            let split = match extract_split_from_pb_role_name(&role.name) {
-                Some(split) => split,
-                None => {
-                    eprintln!(
-                        "RoleSelectionMessageSendError: get pb split from role name: '{}'.",
-                        role.name
-                    );
-                    return false;
-                }
-            };
-            return split == Split::FirstStructure;
+    // Nether, first structure and finish roles are optional, so their pickers are only
+    // sent when the guild has roles for them.
+    let pickers = [
+        (
+            Split::Nether,
+            "select_nether_role",
+            "Choose a Nether Role...",
+            true,
+        ),
+        (
+            Split::FirstStructure,
+            "select_structure1_role",
+            "Choose a First Structure Role...",
+            true,
+        ),
+        (
+            Split::SecondStructure,
+            "select_structure2_role",
+            "Choose a Second Structure Role...",
+            false,
+        ),
+        (
+            Split::Blind,
+            "select_blind_role",
+            "Choose a Blind Role...",
+            false,
+        ),
+        (
+            Split::EyeSpy,
+            "select_eye_spy_role",
+            "Choose an Eye Spy Role...",
+            false,
+        ),
+        (
+            Split::EndEnter,
+            "select_end_enter_role",
+            "Choose an End Enter Role...",
+            false,
+        ),
+        (
+            Split::Finish,
+            "select_finish_role",
+            "Choose a Finish Role...",
+            true,
+        ),
+    ];
+    let mut action_rows = vec![];
+    for (split, custom_id, placeholder, is_optional) in pickers {
+        if is_optional && !has_roles_for_split(&roles, &split) {
+            continue;
         }
-        let (split, _minutes, _seconds) = match extract_split_from_role_name(&role.name) {
-            Ok(tup) => tup,
-            Err(err) => {
-                eprintln!(
-                    "RoleSelectionMessageSendError: get split from role name: '{}': {}",
-                    role.name, err
-                );
-                return false;
-            }
-        };
-        split == Split::FirstStructure
-    });
-
-    select_bastion_role_action_row.create_select_menu(|m| {
-        m.custom_id("select_structure1_role")
-            .placeholder("Choose a First Structure Role...")
-            .options(|o| {
-                match create_select_option(o, &roles, Split::FirstStructure) {
-                    Ok(_) => (),
-                    Err(err) => {
-                        eprintln!("RoleSelectionMessageSendError: {}", err);
-                    }
-                }
-                o
-            })
-    });
-    select_fortress_role_action_row.create_select_menu(|m| {
-        m.custom_id("select_structure2_role")
-            .placeholder("Choose a Second Structure Role...")
-            .options(|o| {
-                match create_select_option(o, &roles, Split::SecondStructure) {
-                    Ok(_) => (),
-                    Err(err) => {
-                        eprintln!("RoleSelectionMessageSendError: {}", err);
-                    }
-                }
-                o
-            })
-    });
-    select_blind_role_action_row.create_select_menu(|m| {
-        m.custom_id("select_blind_role")
-            .placeholder("Choose a Blind Role...")
-            .options(|o| {
-                match create_select_option(o, &roles, Split::Blind) {
-                    Ok(_) => (),
-                    Err(err) => {
-                        eprintln!("RoleSelectionMessageSendError: {}", err);
-                    }
-                }
-                o
-            })
-    });
-    select_eye_spy_role_action_row.create_select_menu(|m| {
-        m.custom_id("select_eye_spy_role")
-            .placeholder("Choose an Eye Spy Role...")
-            .options(|o| {
-                match create_select_option(o, &roles, Split::EyeSpy) {
-                    Ok(_) => (),
-                    Err(err) => {
-                        eprintln!("RoleSelectionMessageSendError: {}", err);
-                    }
-                }
-                o
-            })
-    });
-    select_end_enter_role_action_row.create_select_menu(|m| {
-        m.custom_id("select_end_enter_role")
-            .placeholder("Choose an End Enter Role...")
-            .options(|o| {
-                match create_select_option(o, &roles, Split::EndEnter) {
-                    Ok(_) => (),
-                    Err(err) => {
-                        eprintln!("RoleSelectionMessageSendError: {}", err);
+        let mut action_row = CreateActionRow::default();
+        action_row.create_select_menu(|m| {
+            m.custom_id(custom_id)
+                .placeholder(placeholder)
+                .options(|o| {
+                    match create_select_option(o, &roles, split) {
+                        Ok(_) => (),
+                        Err(err) => {
+                            eprintln!("RoleSelectionMessageSendError: {}", err);
+                        }
                     }
-                }
-                o
-            })
-    });
+                    o
+                })
+        });
+        action_rows.push(action_row);
+    }
     let mut remove_roles_action_row = CreateActionRow::default();
 
     remove_roles_action_row.create_button(|c| {
@@ -175,26 +144,17 @@ pub async fn send_role_selection_message(
             .label("Remove ALL PMB Roles")
             .custom_id("remove_pmb_roles")
     });
+    action_rows.push(remove_roles_action_row);
 
     let content = "Select roles based on the splits and paces you wish to follow.";
 
+    // A message can only hold so many action rows, the rest are sent as follow ups.
+    let mut action_row_chunks = action_rows.chunks(MAX_ACTION_ROWS);
+    let first_action_rows = action_row_chunks.next().unwrap_or_default().to_vec();
     match command
         .edit_original_interaction_response(&ctx.http, |data| {
-            data.content(content).components(|c| {
-                if send_bastion_picker {
-                    c.add_action_row(select_bastion_role_action_row)
-                        .add_action_row(select_fortress_role_action_row)
-                        .add_action_row(select_blind_role_action_row)
-                        .add_action_row(select_eye_spy_role_action_row)
-                        .add_action_row(select_end_enter_role_action_row)
-                } else {
-                    c.add_action_row(select_fortress_role_action_row)
-                        .add_action_row(select_blind_role_action_row)
-                        .add_action_row(select_eye_spy_role_action_row)
-                        .add_action_row(select_end_enter_role_action_row)
-                        .add_action_row(remove_roles_action_row.to_owned())
-                }
-            })
+            data.content(content)
+                .components(|c| c.set_action_rows(first_action_rows))
         })
         .await
     {
@@ -210,14 +170,43 @@ pub async fn send_role_selection_message(
             return Err(content.into());
         }
     };
-    if send_bastion_picker {
+    for action_rows in action_row_chunks {
         command
             .channel_id
             .send_message(&ctx.http, |m| {
                 m.content("")
-                    .components(|c| c.add_action_row(remove_roles_action_row))
+                    .components(|c| c.set_action_rows(action_rows.to_vec()))
             })
             .await?;
     }
     Ok(())
 }
+
+fn has_roles_for_split(roles: &[&Role], target_split: &Split) -> bool {
+    roles.iter().any(|role| {
+        if role.name.contains("PB") {
+            let split = match extract_split_from_pb_role_name(&role.name) {
+                Some(split) => split,
+                None => {
+                    eprintln!(
+                        "RoleSelectionMessageSendError: get pb split from role name: '{}'.",
+                        role.name
+                    );
+                    return false;
+                }
+            };
+            return split == *target_split;
+        }
+        let (split, _minutes, _seconds) = match extract_split_from_role_name(&role.name) {
+            Ok(tup) => tup,
+            Err(err) => {
+                eprintln!(
+                    "RoleSelectionMessageSendError: get split from role name: '{}': {}",
+                    role.name, err
+                );
+                return false;
+            }
+        };
+        split == *target_split
+    })
+}
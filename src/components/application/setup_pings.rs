@@ -4,7 +4,8 @@ use serenity::{
 };
 
 use crate::{
-    cache::{guild_data::GuildData, split::Split},
+    cache::{split::Split, CacheManager},
+    handler::ArcMutex,
     utils::{create_guild_role::create_guild_role, remove_runner_pings::remove_runner_pings},
     Result,
 };
@@ -13,6 +14,7 @@ pub async fn setup_pings(
     ctx: &Context,
     guild_id: GuildId,
     command: &ApplicationCommandInteraction,
+    cache_manager: ArcMutex<CacheManager>,
 ) -> Result<()> {
     command.defer_ephemeral(&ctx).await?;
     let mut action = String::new();
@@ -76,7 +78,17 @@ pub async fn setup_pings(
             return Err(format!("SetupPingsError: construct Split from str: '{}'.", split).into())
         }
     };
-    let guild_data = GuildData::new(&ctx, guild_id).await?;
+    let locked_cache_manager = cache_manager.lock().await;
+    let guild_data = match locked_cache_manager.cache.get(&guild_id) {
+        Some(guild_data) => guild_data,
+        None => {
+            return Err(format!(
+                "SetupPingsError: get cached guild for guild id: {}",
+                guild_id
+            )
+            .into())
+        }
+    };
     if guild_data.is_private && !guild_data.players.contains_key(&ign.to_lowercase()) {
         let response_content = format!(
             "SetupPingsError: Runner with name: '{}' not found in guild.",
@@ -84,6 +96,7 @@ pub async fn setup_pings(
         );
         return Err(response_content.into());
     }
+    drop(locked_cache_manager);
     let mut sender = match command.member.to_owned() {
         Some(sender) => sender,
         None => return Err("SetupPingsError: get member for '/setup_pings'.".into()),
@@ -0,0 +1,141 @@
+use serenity::{
+    client::Context,
+    model::{id::GuildId, prelude::application_command::ApplicationCommandInteraction},
+};
+
+use crate::{
+    cache::{split::Split, subscription::Subscription, CacheManager},
+    handler::ArcMutex,
+    utils::parse_time::parse_time,
+    Result,
+};
+
+pub async fn subscribe(
+    ctx: &Context,
+    guild_id: GuildId,
+    command: &ApplicationCommandInteraction,
+    cache_manager: ArcMutex<CacheManager>,
+) -> Result<()> {
+    command.defer_ephemeral(&ctx).await?;
+    let mut action = String::new();
+    let mut runner = None;
+    let mut split = None;
+    let mut time = None;
+    let mut id = None;
+    for option in command.data.options.iter() {
+        let value = match option.value.to_owned() {
+            Some(value) => value,
+            None => {
+                return Err(
+                    format!("SubscribeError: get value for option: '{}'.", option.name).into(),
+                )
+            }
+        };
+        if option.name == "id" {
+            id = match value.as_i64() {
+                Some(int) => Some(int),
+                None => return Err("SubscribeError: parse i64 for id option.".into()),
+            };
+            continue;
+        }
+        let value = match value.as_str() {
+            Some(str) => str.to_owned(),
+            None => {
+                return Err(format!(
+                    "SubscribeError: parse string for option: '{}'.",
+                    option.name
+                )
+                .into())
+            }
+        };
+        match option.name.as_str() {
+            "action" => action = value,
+            "runner" => runner = Some(value.trim().to_owned()),
+            "split" => {
+                split = match Split::from_str(value.as_str()) {
+                    Some(split) => Some(split),
+                    None => {
+                        return Err(
+                            format!("SubscribeError: Unrecognized split: '{}'.", value).into()
+                        )
+                    }
+                }
+            }
+            "time" => time = Some(value),
+            _ => return Err(format!("Unrecognized command option: '{}'", option.name).into()),
+        };
+    }
+
+    let user_id = command.user.id;
+    let mut locked_cache_manager = cache_manager.lock().await;
+    let response_content = match (action.as_str(), runner, split, time) {
+        ("add", Some(runner), Some(split), Some(time)) => match parse_time(time.as_str()) {
+            Ok(time) => {
+                let subscription = Subscription {
+                    id: 0,
+                    user_id,
+                    runner,
+                    split,
+                    time,
+                };
+                let id = locked_cache_manager
+                    .db
+                    .add_subscription(guild_id, &subscription)?;
+                Ok(format!(
+                    "Subscribed! You will get a DM for {}.",
+                    Subscription { id, ..subscription }
+                ))
+            }
+            Err(err) => Err(format!("SubscribeError: Invalid time: {}", err)),
+        },
+        ("add", _, _, _) => Err(
+            "SubscribeError: Parameters 'runner', 'split' and 'time' are needed for 'add'."
+                .to_string(),
+        ),
+        ("remove", _, _, _) => match id {
+            Some(id) => match locked_cache_manager.db.remove_subscription(user_id, id)? {
+                Some(_) => Ok(format!("Removed subscription #{}!", id)),
+                None => Err(format!(
+                    "SubscribeError: You have no subscription with id: {}",
+                    id
+                )),
+            },
+            None => Err("SubscribeError: Parameter 'id' is undefined for 'remove'.".to_string()),
+        },
+        _ => Ok(String::new()),
+    };
+    let response_content = match response_content {
+        Ok(content) => content,
+        Err(response_content) => {
+            drop(locked_cache_manager);
+            command
+                .edit_original_interaction_response(&ctx.http, |m| {
+                    m.content(response_content.to_string())
+                })
+                .await?;
+            return Err(response_content.into());
+        }
+    };
+    locked_cache_manager.add_or_update_guild(ctx, guild_id)?;
+    let subscriptions = match locked_cache_manager.cache.get(&guild_id) {
+        Some(guild_data) => guild_data
+            .subscriptions
+            .iter()
+            .filter(|subscription| subscription.user_id == user_id)
+            .map(|subscription| format!("- {}", subscription))
+            .collect::<Vec<_>>(),
+        None => vec![],
+    };
+    drop(locked_cache_manager);
+    let subscriptions_content = if subscriptions.is_empty() {
+        "You have no subscriptions in this server.".to_string()
+    } else {
+        format!("Your subscriptions:\n{}", subscriptions.join("\n"))
+    };
+    command
+        .edit_original_interaction_response(&ctx.http, |m| {
+            m.content(format!("{}\n{}", response_content, subscriptions_content).trim())
+        })
+        .await?;
+    Ok(())
+}
@@ -1,24 +1,38 @@
 use serenity::{
     client::Context,
     model::{id::GuildId, prelude::application_command::ApplicationCommandInteraction},
+    prelude::Mentionable,
 };
 
-use crate::{cache::guild_data::GuildData, Result};
+use crate::{
+    cache::{guild_data::GuildData, CacheManager},
+    handler::ArcMutex,
+    ws::connection_state::ConnectionState,
+    Result,
+};
 
 pub async fn validate_config(
     ctx: &Context,
     guild_id: GuildId,
     command: &ApplicationCommandInteraction,
+    cache_manager: ArcMutex<CacheManager>,
+    connection_state: ArcMutex<ConnectionState>,
 ) -> Result<()> {
     command.defer_ephemeral(&ctx).await?;
     let reply_content;
-    match GuildData::new(&ctx, guild_id).await {
-        Ok(_) => {
-            reply_content =
-                "Config validation successful! Bot will send paces in #pacemanbot.".to_string()
+    let locked_cache_manager = cache_manager.lock().await;
+    match GuildData::new(&ctx, guild_id, &locked_cache_manager.db) {
+        Ok(guild_data) => {
+            reply_content = format!(
+                "Config validation successful! Bot will send paces in {}.",
+                guild_data.pace_channel.mention()
+            )
         }
         Err(err) => reply_content = format!("Error: {}", err),
     };
+    drop(locked_cache_manager);
+    let connection_state = connection_state.lock().await.to_owned();
+    let reply_content = format!("{}\nStatus: {}.", reply_content, connection_state);
     command
         .edit_original_interaction_response(&ctx, |m| m.content(reply_content))
         .await?;
@@ -4,11 +4,9 @@ use serenity::{
 };
 
 use crate::{
-    cache::players::{PlayerSplitsData, Players},
-    utils::{
-        extract_name_and_splits_from_line::extract_name_and_splits_from_line,
-        get_new_config_contents::get_new_config_contents,
-    },
+    cache::{players::PlayerSplitsData, CacheManager},
+    handler::ArcMutex,
+    utils::{get_minecraft_profile::get_minecraft_profile, parse_time::parse_time},
     Result,
 };
 
@@ -16,14 +14,9 @@ pub async fn whitelist(
     ctx: &Context,
     guild_id: GuildId,
     command: &ApplicationCommandInteraction,
+    cache_manager: ArcMutex<CacheManager>,
 ) -> Result<()> {
     command.defer_ephemeral(&ctx).await?;
-    let channels = match ctx.cache.guild_channels(guild_id) {
-        Some(channels) => channels,
-        None => {
-            return Err(format!("WhitelistError: get channels for guild id: {}", guild_id).into());
-        }
-    };
     let mut action = String::new();
     let mut ign = String::new();
     let mut splits_data = PlayerSplitsData::default();
@@ -66,15 +59,16 @@ pub async fn whitelist(
             },
             "first_structure" => match option.value.to_owned() {
                 Some(value) => {
-                    splits_data.first_structure = match value.as_u64() {
-                        Some(int) => int as u8,
-                        None => {
-                            return Err(String::from(
-                                "WhitelistError: parse u64 for first structure option.",
-                            )
-                            .into())
+                    splits_data.first_structure =
+                        match value.as_str().and_then(|time| parse_time(time).ok()) {
+                            Some(millis) => millis,
+                            None => {
+                                return Err(String::from(
+                                    "WhitelistError: parse time for first structure option.",
+                                )
+                                .into())
+                            }
                         }
-                    }
                 }
                 None => {
                     if action != "remove" {
@@ -87,15 +81,16 @@ pub async fn whitelist(
             },
             "second_structure" => match option.value.to_owned() {
                 Some(value) => {
-                    splits_data.second_structure = match value.as_u64() {
-                        Some(int) => int as u8,
-                        None => {
-                            return Err(String::from(
-                                "WhitelistError: parse u64 for second structure option.",
-                            )
-                            .into())
+                    splits_data.second_structure =
+                        match value.as_str().and_then(|time| parse_time(time).ok()) {
+                            Some(millis) => millis,
+                            None => {
+                                return Err(String::from(
+                                    "WhitelistError: parse time for second structure option.",
+                                )
+                                .into())
+                            }
                         }
-                    }
                 }
                 None => {
                     if action != "remove" {
@@ -108,12 +103,14 @@ pub async fn whitelist(
             },
             "blind" => match option.value.to_owned() {
                 Some(value) => {
-                    splits_data.blind = match value.as_u64() {
-                        Some(int) => int as u8,
+                    splits_data.blind = match value.as_str().and_then(|time| parse_time(time).ok())
+                    {
+                        Some(millis) => millis,
                         None => {
-                            return Err(
-                                String::from("WhitelistError: parse u64 for blind option.").into()
+                            return Err(String::from(
+                                "WhitelistError: parse time for blind option.",
                             )
+                            .into())
                         }
                     }
                 }
@@ -127,15 +124,16 @@ pub async fn whitelist(
             },
             "eye_spy" => match option.value.to_owned() {
                 Some(value) => {
-                    splits_data.eye_spy = match value.as_u64() {
-                        Some(int) => int as u8,
-                        None => {
-                            return Err(String::from(
-                                "WhitelistError: parse u64 for eye spy option.",
-                            )
-                            .into())
+                    splits_data.eye_spy =
+                        match value.as_str().and_then(|time| parse_time(time).ok()) {
+                            Some(millis) => millis,
+                            None => {
+                                return Err(String::from(
+                                    "WhitelistError: parse time for eye spy option.",
+                                )
+                                .into())
+                            }
                         }
-                    }
                 }
                 None => {
                     if action != "remove" {
@@ -147,15 +145,16 @@ pub async fn whitelist(
             },
             "end_enter" => match option.value.to_owned() {
                 Some(value) => {
-                    splits_data.end_enter = match value.as_u64() {
-                        Some(int) => int as u8,
-                        None => {
-                            return Err(String::from(
-                                "WhitelistError: parse u64 for end enter option.",
-                            )
-                            .into())
+                    splits_data.end_enter =
+                        match value.as_str().and_then(|time| parse_time(time).ok()) {
+                            Some(millis) => millis,
+                            None => {
+                                return Err(String::from(
+                                    "WhitelistError: parse time for end enter option.",
+                                )
+                                .into())
+                            }
                         }
-                    }
                 }
                 None => {
                     if action != "remove" {
@@ -168,11 +167,12 @@ pub async fn whitelist(
             },
             "finish" => match option.value.to_owned() {
                 Some(value) => {
-                    splits_data.finish = match value.as_u64() {
-                        Some(int) => Some(int as u8),
+                    splits_data.finish = match value.as_str().and_then(|time| parse_time(time).ok())
+                    {
+                        Some(millis) => Some(millis),
                         None => {
                             return Err(String::from(
-                                "WhitelistError: parse u64 for end enter option.",
+                                "WhitelistError: parse time for finish option.",
                             )
                             .into())
                         }
@@ -184,79 +184,62 @@ pub async fn whitelist(
         };
     }
 
-    let channel = channels
-        .iter()
-        .filter(|c| c.name == "pacemanbot-runner-names")
-        .collect::<Vec<_>>();
-    let channel = match channel.first() {
-        Some(channel) => channel,
-        None => {
-            return Err(format!(
-                "WhitelistError: find #pacemanbot-runner-names in guild id: {}",
-                guild_id
-            )
-            .into())
-        }
+    // Runners that can't be looked up are linked to their account on their first run.
+    let profile = match action.as_str() {
+        "remove" => None,
+        _ => get_minecraft_profile(ign.trim())
+            .await
+            .map_err(|err| err.to_string())
+            .unwrap_or_else(|err| {
+                eprintln!("WhitelistError: look up runner: '{}' due to: {}", ign, err);
+                None
+            }),
     };
-    let message = channel.messages(&ctx.http, |m| m.limit(1)).await?;
-    let mut players: Players = Players::new();
-    match message.last() {
-        Some(message) => {
-            if !message.author.bot {
-                let response_content = String::from(
-                    "WhitelistError: The first message in #pacemanbot-runner-names is not from the bot.",
-                );
-                command
-                    .edit_original_interaction_response(&ctx.http, |m| {
-                        m.content(response_content.to_string())
-                    })
-                    .await?;
-                return Err(response_content.into());
-            }
-            for line in message.content.split("\n") {
-                if line == "```" || line == "" {
-                    continue;
-                }
-                let (name, split_data) = extract_name_and_splits_from_line(line)?;
-                players.insert(name, split_data);
-            }
-            if action == "remove" {
-                players.remove(&ign);
-            } else {
-                players.insert(ign, splits_data);
-            }
-            let new_config = get_new_config_contents(players);
-            message
-                .to_owned()
-                .edit(&ctx.http, |m| {
-                    m.content(format!("```\n{}\n```", new_config))
+    let mut response_content = "Updated config!".to_string();
+    let mut locked_cache_manager = cache_manager.lock().await;
+    if action == "remove" {
+        if !locked_cache_manager
+            .db
+            .remove_player(guild_id, ign.as_str())?
+        {
+            let response_content = format!(
+                "WhitelistError: No runner with name: '{}' to remove in guild id: {}",
+                ign, guild_id
+            );
+            drop(locked_cache_manager);
+            command
+                .edit_original_interaction_response(&ctx.http, |m| {
+                    m.content(response_content.to_string())
                 })
                 .await?;
+            return Err(response_content.into());
         }
-        None => {
-            if action == "remove" {
-                let response_content = format!(
-                    "WhitelistError: No names to remove from in guild id: {}",
-                    guild_id
+    } else {
+        match &profile {
+            Some((uuid, name)) => locked_cache_manager.db.upsert_player(
+                guild_id,
+                name.as_str(),
+                Some(uuid.as_str()),
+                &splits_data,
+            )?,
+            None => {
+                locked_cache_manager.db.upsert_player(
+                    guild_id,
+                    ign.as_str(),
+                    None,
+                    &splits_data,
+                )?;
+                response_content = format!(
+                    "{}\nCould not find the Minecraft account of '{}', so the runner will be linked to their account on their first run.",
+                    response_content, ign
                 );
-                command
-                    .edit_original_interaction_response(&ctx.http, |m| {
-                        m.content(response_content.to_string())
-                    })
-                    .await?;
-                return Err(response_content.into());
             }
-            players.insert(ign, splits_data);
-            let new_config = get_new_config_contents(players);
-            channel
-                .send_message(&ctx.http, |m| {
-                    m.content(format!("```\n{}\n```", new_config))
-                })
-                .await?;
-        }
-    };
+        };
+    }
+    locked_cache_manager.add_or_update_guild(ctx, guild_id)?;
+    drop(locked_cache_manager);
     command
-        .edit_original_interaction_response(&ctx.http, |m| m.content("Updated config!"))
+        .edit_original_interaction_response(&ctx.http, |m| m.content(response_content))
         .await?;
     Ok(())
 }
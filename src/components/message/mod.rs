@@ -1,2 +1,3 @@
 pub mod remove_pmb_roles;
 pub mod select_role;
+pub mod unsubscribe;
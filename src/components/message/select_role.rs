@@ -4,7 +4,12 @@ use serenity::{
 };
 
 use crate::{
-    cache::split::Split, utils::remove_roles_starting_with::remove_roles_starting_with, Result,
+    cache::split::Split,
+    utils::{
+        extract_split_from_pb_role_name::extract_split_from_pb_role_name,
+        extract_split_from_role_name::extract_split_from_role_name,
+    },
+    Result,
 };
 
 pub async fn handle_select_role(
@@ -12,7 +17,6 @@ pub async fn handle_select_role(
     message_component: &MessageComponentInteraction,
     split: Split,
 ) -> Result<()> {
-    let split_str = split.to_str();
     let guild_id = match message_component.guild_id {
         Some(guild_id) => guild_id,
         None => {
@@ -55,30 +59,34 @@ pub async fn handle_select_role(
         roles_to_add.push(role_id);
     }
 
-    if remove_roles {
-        remove_roles_starting_with(
-            &ctx,
-            &guild_id,
-            &mut member,
-            format!("*{}", split_str).as_str(),
-            true,
-        )
-        .await?;
-    } else {
-        let member_roles = match member.roles(&ctx) {
-            Some(roles) => roles,
-            None => {
-                return Err(format!(
-                    "SelectRoleError: get roles for member with name: {}.",
-                    member.display_name()
-                )
-                .into())
+    let member_roles = match member.roles(&ctx) {
+        Some(roles) => roles,
+        None => {
+            return Err(format!(
+                "SelectRoleError: get roles for member with name: {}.",
+                member.display_name()
+            )
+            .into())
+        }
+    };
+    // Splits share prefixes (e.g. '*F' and '*FS'), so roles are matched on their
+    // parsed split instead of the raw name.
+    for role in member_roles.iter().filter(|r| r.name.starts_with("*")) {
+        let role_split = if role.name.contains("PB") {
+            if remove_roles {
+                continue;
             }
-        };
-        for role in member_roles {
-            if role.name.starts_with(&format!("*{}", split_str)) && role.name.contains("PB") {
-                member.remove_role(&ctx, role.id).await?;
+            extract_split_from_pb_role_name(&role.name)
+        } else {
+            if !remove_roles {
+                continue;
             }
+            extract_split_from_role_name(&role.name)
+                .ok()
+                .map(|(split, _, _)| split)
+        };
+        if role_split.as_ref() == Some(&split) {
+            member.remove_role(&ctx, role.id).await?;
         }
     }
 
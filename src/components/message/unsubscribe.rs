@@ -0,0 +1,32 @@
+use serenity::{client::Context, model::prelude::message_component::MessageComponentInteraction};
+
+use crate::{cache::CacheManager, handler::ArcMutex, Result};
+
+pub async fn handle_unsubscribe(
+    ctx: &Context,
+    message_component: &MessageComponentInteraction,
+    subscription_id: i64,
+    cache_manager: ArcMutex<CacheManager>,
+) -> Result<()> {
+    let mut locked_cache_manager = cache_manager.lock().await;
+    let guild_id = locked_cache_manager
+        .db
+        .remove_subscription(message_component.user.id, subscription_id)?;
+    let response_content = match guild_id {
+        Some(guild_id) => {
+            if let Err(err) = locked_cache_manager.add_or_update_guild(ctx, guild_id) {
+                eprintln!(
+                    "UnsubscribeError: update guild id: {} due to: {}",
+                    guild_id, err
+                );
+            }
+            "Unsubscribed! You won't get these DMs anymore."
+        }
+        None => "You are already unsubscribed.",
+    };
+    drop(locked_cache_manager);
+    message_component
+        .edit_original_interaction_response(&ctx.http, |m| m.content(response_content))
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,95 @@
+pub const DATABASE_FALLBACK_PATH: &str = "pacemanbot.db";
+pub const CONFIG_IMPORTED_SETTING: &str = "config_imported";
+pub const EVOLVING_MESSAGES_SETTING: &str = "evolving_messages";
+pub const RESET_PING_WINDOW_SETTING: &str = "reset_ping_window";
+pub const EMBED_TEMPLATE_SETTING: &str = "embed_template";
+pub const LEADERBOARD_PERIOD_SETTING: &str = "leaderboard_period";
+pub const LEADERBOARD_ROLLOVER_SETTING: &str = "leaderboard_rollover";
+pub const LEADERBOARD_IMPORTED_SETTING: &str = "leaderboard_imported";
+// Imported runs of runners without a known uuid are keyed by their name instead.
+pub const LEADERBOARD_LEGACY_UUID_PREFIX: &str = "name:";
+
+// Each entry upgrades the schema by one version, tracked with `PRAGMA user_version`.
+pub const MIGRATIONS: &[&str] = &[
+    "
+    CREATE TABLE runners (
+        guild_id INTEGER NOT NULL,
+        name TEXT NOT NULL COLLATE NOCASE,
+        first_structure INTEGER NOT NULL,
+        second_structure INTEGER NOT NULL,
+        blind INTEGER NOT NULL,
+        eye_spy INTEGER NOT NULL,
+        end_enter INTEGER NOT NULL,
+        finish INTEGER,
+        PRIMARY KEY (guild_id, name)
+    );
+    CREATE TABLE settings (
+        guild_id INTEGER NOT NULL,
+        key TEXT NOT NULL,
+        value TEXT NOT NULL,
+        PRIMARY KEY (guild_id, key)
+    );
+",
+    "
+    CREATE TABLE item_requirements (
+        guild_id INTEGER NOT NULL,
+        target TEXT NOT NULL,
+        item TEXT NOT NULL,
+        min_count INTEGER NOT NULL,
+        PRIMARY KEY (guild_id, target, item)
+    );
+",
+    "
+    CREATE TABLE rules (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        guild_id INTEGER NOT NULL,
+        source TEXT NOT NULL
+    );
+",
+    "
+    UPDATE runners SET
+        first_structure = first_structure * 60000,
+        second_structure = second_structure * 60000,
+        blind = blind * 60000,
+        eye_spy = eye_spy * 60000,
+        end_enter = end_enter * 60000,
+        finish = finish * 60000;
+",
+    "
+    CREATE TABLE routes (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        guild_id INTEGER NOT NULL,
+        channel_id INTEGER NOT NULL,
+        split TEXT,
+        runner TEXT COLLATE NOCASE,
+        run_type TEXT
+    );
+",
+    "
+    CREATE TABLE subscriptions (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        guild_id INTEGER NOT NULL,
+        user_id INTEGER NOT NULL,
+        runner TEXT NOT NULL COLLATE NOCASE,
+        split TEXT NOT NULL,
+        time INTEGER NOT NULL
+    );
+",
+    "
+    CREATE TABLE leaderboard_runs (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        guild_id INTEGER NOT NULL,
+        uuid TEXT NOT NULL,
+        runner TEXT NOT NULL,
+        split TEXT NOT NULL,
+        time INTEGER NOT NULL,
+        world_id TEXT,
+        date INTEGER NOT NULL
+    );
+    CREATE INDEX leaderboard_runs_guild ON leaderboard_runs (guild_id, uuid, split);
+",
+    "
+    ALTER TABLE runners ADD COLUMN uuid TEXT;
+    CREATE INDEX runners_uuid ON runners (guild_id, uuid);
+",
+];
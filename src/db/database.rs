@@ -0,0 +1,72 @@
+use std::{
+    env,
+    sync::{Mutex, MutexGuard},
+};
+
+use rusqlite::Connection;
+
+use crate::Result;
+
+use super::{
+    consts::{DATABASE_FALLBACK_PATH, MIGRATIONS},
+    Database,
+};
+
+impl Database {
+    pub fn lock(&self) -> Result<MutexGuard<'_, Connection>> {
+        match self.conn.lock() {
+            Ok(conn) => Ok(conn),
+            Err(err) => Err(format!("DatabaseError: lock connection due to: {}", err).into()),
+        }
+    }
+
+    fn migrate(&self) -> Result<()> {
+        let mut conn = self.lock()?;
+        let version: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        for (idx, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+            // The version is bumped in the same transaction, so a migration is never
+            // applied twice even if the bot stops halfway.
+            let tx = conn.transaction()?;
+            match tx.execute_batch(migration) {
+                Ok(_) => (),
+                Err(err) => {
+                    return Err(format!(
+                        "DatabaseError: apply migration to version: {} due to: {}",
+                        idx + 1,
+                        err
+                    )
+                    .into())
+                }
+            };
+            tx.pragma_update(None, "user_version", (idx + 1) as i64)?;
+            tx.commit()?;
+        }
+        Ok(())
+    }
+
+    pub fn open() -> Result<Self> {
+        let path = env::var("DATABASE_PATH").unwrap_or(DATABASE_FALLBACK_PATH.to_string());
+        let conn = match Connection::open(&path) {
+            Ok(conn) => conn,
+            Err(err) => {
+                return Err(
+                    format!("DatabaseError: open database: '{}' due to: {}", path, err).into(),
+                )
+            }
+        };
+        let database = Self {
+            conn: Mutex::new(conn),
+        };
+        database.migrate()?;
+        Ok(database)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self> {
+        let database = Self {
+            conn: Mutex::new(Connection::open_in_memory()?),
+        };
+        database.migrate()?;
+        Ok(database)
+    }
+}
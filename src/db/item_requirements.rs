@@ -0,0 +1,78 @@
+use rusqlite::params;
+use serenity::model::id::GuildId;
+
+use crate::{
+    cache::item_requirement::{ItemRequirement, RequirementTarget},
+    Result,
+};
+
+use super::Database;
+
+impl Database {
+    pub fn get_item_requirements(&self, guild_id: GuildId) -> Result<Vec<ItemRequirement>> {
+        let conn = self.lock()?;
+        let mut statement = conn
+            .prepare("SELECT target, item, min_count FROM item_requirements WHERE guild_id = ?1")?;
+        let rows = statement.query_map(params![guild_id.0 as i64], |row| {
+            let target: String = row.get(0)?;
+            let item: String = row.get(1)?;
+            let min_count: u32 = row.get(2)?;
+            Ok((target, item, min_count))
+        })?;
+        let mut requirements = vec![];
+        for row in rows {
+            let (target, item, min_count) = row?;
+            let target = match RequirementTarget::from_str(target.as_str()) {
+                Some(target) => target,
+                None => {
+                    eprintln!(
+                        "DatabaseError: parse item requirement target: '{}' in guild id: {}",
+                        target, guild_id
+                    );
+                    continue;
+                }
+            };
+            requirements.push(ItemRequirement {
+                target,
+                item: ItemRequirement::item_from_id(item.as_str()),
+                min_count,
+            });
+        }
+        Ok(requirements)
+    }
+
+    pub fn upsert_item_requirement(
+        &self,
+        guild_id: GuildId,
+        requirement: &ItemRequirement,
+    ) -> Result<()> {
+        self.lock()?.execute(
+            "INSERT INTO item_requirements (guild_id, target, item, min_count)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (guild_id, target, item) DO UPDATE SET min_count = excluded.min_count",
+            params![
+                guild_id.0 as i64,
+                requirement.target.to_str(),
+                requirement.item_id(),
+                requirement.min_count
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn remove_item_requirement(
+        &self,
+        guild_id: GuildId,
+        requirement: &ItemRequirement,
+    ) -> Result<bool> {
+        let removed = self.lock()?.execute(
+            "DELETE FROM item_requirements WHERE guild_id = ?1 AND target = ?2 AND item = ?3",
+            params![
+                guild_id.0 as i64,
+                requirement.target.to_str(),
+                requirement.item_id()
+            ],
+        )?;
+        Ok(removed > 0)
+    }
+}
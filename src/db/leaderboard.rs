@@ -0,0 +1,189 @@
+use rusqlite::{params, OptionalExtension};
+use serenity::model::id::GuildId;
+
+use crate::{
+    cache::{
+        leaderboard::{Leaderboard, LeaderboardEntry},
+        split::Split,
+    },
+    Result,
+};
+
+use super::{consts::LEADERBOARD_LEGACY_UUID_PREFIX, Database};
+
+impl Database {
+    // Only runs between the start and end of the range count, if there is one.
+    pub fn get_leaderboard(
+        &self,
+        guild_id: GuildId,
+        range: Option<(i64, i64)>,
+    ) -> Result<Leaderboard> {
+        let conn = self.lock()?;
+        // SQLite takes the other columns from the row with the minimum time.
+        let mut statement = conn.prepare(
+            "SELECT uuid, runner, split, MIN(time), world_id, date FROM leaderboard_runs
+            WHERE guild_id = ?1 AND (?2 IS NULL OR date >= ?2) AND (?3 IS NULL OR date < ?3)
+            GROUP BY uuid, split",
+        )?;
+        let (start, end) = range.unzip();
+        let rows = statement.query_map(params![guild_id.0 as i64, start, end], |row| {
+            let uuid: String = row.get(0)?;
+            let runner: String = row.get(1)?;
+            let split: String = row.get(2)?;
+            let time: u64 = row.get(3)?;
+            let world_id: Option<String> = row.get(4)?;
+            let date: i64 = row.get(5)?;
+            Ok((uuid, runner, split, time, world_id, date))
+        })?;
+        let mut bests = vec![];
+        for row in rows {
+            let (uuid, runner, split, time, world_id, date) = row?;
+            let split = match Split::from_str(split.as_str()) {
+                Some(split) => split,
+                None => {
+                    eprintln!(
+                        "DatabaseError: parse leaderboard split: '{}' in guild id: {}",
+                        split, guild_id
+                    );
+                    continue;
+                }
+            };
+            bests.push(LeaderboardEntry {
+                uuid,
+                runner,
+                split,
+                time,
+                world_id,
+                date,
+            });
+        }
+        Ok(Leaderboard { bests })
+    }
+
+    // Returns whether the run is a new best for the runner on that split, counting
+    // only the runs since the given time if there is one.
+    pub fn add_leaderboard_run(
+        &self,
+        guild_id: GuildId,
+        entry: &LeaderboardEntry,
+        since: Option<i64>,
+    ) -> Result<bool> {
+        let conn = self.lock()?;
+        // Imported runs are handed over to the runner once their uuid is known.
+        conn.execute(
+            "UPDATE leaderboard_runs SET uuid = ?2 WHERE guild_id = ?1 AND uuid = ?3",
+            params![
+                guild_id.0 as i64,
+                entry.uuid,
+                format!(
+                    "{}{}",
+                    LEADERBOARD_LEGACY_UUID_PREFIX,
+                    entry.runner.to_lowercase()
+                ),
+            ],
+        )?;
+        let best: Option<u64> = conn.query_row(
+            "SELECT MIN(time) FROM leaderboard_runs
+            WHERE guild_id = ?1 AND uuid = ?2 AND split = ?3 AND (?4 IS NULL OR date >= ?4)",
+            params![guild_id.0 as i64, entry.uuid, entry.split.to_str(), since],
+            |row| row.get(0),
+        )?;
+        conn.execute(
+            "INSERT INTO leaderboard_runs (guild_id, uuid, runner, split, time, world_id, date)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                guild_id.0 as i64,
+                entry.uuid,
+                entry.runner,
+                entry.split.to_str(),
+                entry.time,
+                entry.world_id,
+                entry.date,
+            ],
+        )?;
+        // Older runs are shown with the name the runner uses now.
+        conn.execute(
+            "UPDATE leaderboard_runs SET runner = ?3 WHERE guild_id = ?1 AND uuid = ?2",
+            params![guild_id.0 as i64, entry.uuid, entry.runner],
+        )?;
+        Ok(best.is_none_or(|best| entry.time < best))
+    }
+
+    // Runners are looked up by the name they currently use. Returns their uuid and
+    // the name as it is shown on the leaderboard.
+    pub fn find_leaderboard_runner(
+        &self,
+        guild_id: GuildId,
+        runner: &str,
+    ) -> Result<Option<(String, String)>> {
+        let runner = self
+            .lock()?
+            .query_row(
+                "SELECT uuid, runner FROM leaderboard_runs
+                WHERE guild_id = ?1 AND runner = ?2 COLLATE NOCASE LIMIT 1",
+                params![guild_id.0 as i64, runner],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        Ok(runner)
+    }
+
+    pub fn remove_leaderboard_runs(
+        &self,
+        guild_id: GuildId,
+        uuid: &str,
+        split: Option<&Split>,
+    ) -> Result<usize> {
+        let removed = self.lock()?.execute(
+            "DELETE FROM leaderboard_runs
+            WHERE guild_id = ?1 AND uuid = ?2 AND (?3 IS NULL OR split = ?3)",
+            params![guild_id.0 as i64, uuid, split.map(|split| split.to_str())],
+        )?;
+        Ok(removed)
+    }
+
+    // Corrects the best time of a runner on a split. Only the runs that are faster than
+    // the corrected time are removed, every other run is kept. Returns how many runs
+    // were removed.
+    pub fn set_leaderboard_best(
+        &self,
+        guild_id: GuildId,
+        entry: &LeaderboardEntry,
+    ) -> Result<usize> {
+        let mut conn = self.lock()?;
+        let tx = conn.transaction()?;
+        let removed = tx.execute(
+            "DELETE FROM leaderboard_runs
+            WHERE guild_id = ?1 AND uuid = ?2 AND split = ?3 AND time < ?4",
+            params![
+                guild_id.0 as i64,
+                entry.uuid,
+                entry.split.to_str(),
+                entry.time
+            ],
+        )?;
+        tx.execute(
+            "INSERT INTO leaderboard_runs (guild_id, uuid, runner, split, time, world_id, date)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                guild_id.0 as i64,
+                entry.uuid,
+                entry.runner,
+                entry.split.to_str(),
+                entry.time,
+                entry.world_id,
+                entry.date,
+            ],
+        )?;
+        tx.commit()?;
+        Ok(removed)
+    }
+
+    pub fn reset_leaderboard(&self, guild_id: GuildId) -> Result<usize> {
+        let removed = self.lock()?.execute(
+            "DELETE FROM leaderboard_runs WHERE guild_id = ?1",
+            params![guild_id.0 as i64],
+        )?;
+        Ok(removed)
+    }
+}
@@ -0,0 +1,17 @@
+use std::sync::Mutex;
+
+use rusqlite::Connection;
+
+pub mod consts;
+pub mod database;
+pub mod item_requirements;
+pub mod leaderboard;
+pub mod routes;
+pub mod rules;
+pub mod runners;
+pub mod settings;
+pub mod subscriptions;
+
+pub struct Database {
+    pub conn: Mutex<Connection>,
+}
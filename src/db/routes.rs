@@ -0,0 +1,64 @@
+use rusqlite::params;
+use serenity::model::id::{ChannelId, GuildId};
+
+use crate::{
+    cache::{route::Route, split::Split},
+    dispatcher::run_info::RunType,
+    Result,
+};
+
+use super::Database;
+
+impl Database {
+    pub fn get_routes(&self, guild_id: GuildId) -> Result<Vec<Route>> {
+        let conn = self.lock()?;
+        let mut statement = conn.prepare(
+            "SELECT id, channel_id, split, runner, run_type FROM routes
+            WHERE guild_id = ?1 ORDER BY id",
+        )?;
+        let rows = statement.query_map(params![guild_id.0 as i64], |row| {
+            let id: i64 = row.get(0)?;
+            let channel_id: i64 = row.get(1)?;
+            let split: Option<String> = row.get(2)?;
+            let runner: Option<String> = row.get(3)?;
+            let run_type: Option<String> = row.get(4)?;
+            Ok((id, channel_id, split, runner, run_type))
+        })?;
+        let mut routes = vec![];
+        for row in rows {
+            let (id, channel_id, split, runner, run_type) = row?;
+            routes.push(Route {
+                id,
+                channel_id: ChannelId(channel_id as u64),
+                split: split.as_deref().and_then(Split::from_str),
+                runner,
+                run_type: run_type.as_deref().and_then(RunType::from_command_param),
+            });
+        }
+        Ok(routes)
+    }
+
+    pub fn add_route(&self, guild_id: GuildId, route: &Route) -> Result<i64> {
+        let conn = self.lock()?;
+        conn.execute(
+            "INSERT INTO routes (guild_id, channel_id, split, runner, run_type)
+            VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                guild_id.0 as i64,
+                route.channel_id.0 as i64,
+                route.split.as_ref().map(|split| split.to_str()),
+                route.runner,
+                route.run_type.as_ref().map(|run_type| run_type.to_str()),
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn remove_route(&self, guild_id: GuildId, id: i64) -> Result<bool> {
+        let removed = self.lock()?.execute(
+            "DELETE FROM routes WHERE guild_id = ?1 AND id = ?2",
+            params![guild_id.0 as i64, id],
+        )?;
+        Ok(removed > 0)
+    }
+}
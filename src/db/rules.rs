@@ -0,0 +1,55 @@
+use rusqlite::params;
+use serenity::model::id::GuildId;
+
+use crate::{
+    rules::{parser::parse_rule, GuildRule},
+    Result,
+};
+
+use super::Database;
+
+impl Database {
+    pub fn get_rules(&self, guild_id: GuildId) -> Result<Vec<GuildRule>> {
+        let conn = self.lock()?;
+        let mut statement =
+            conn.prepare("SELECT id, source FROM rules WHERE guild_id = ?1 ORDER BY id")?;
+        let rows = statement.query_map(params![guild_id.0 as i64], |row| {
+            let id: i64 = row.get(0)?;
+            let source: String = row.get(1)?;
+            Ok((id, source))
+        })?;
+        let mut rules = vec![];
+        for row in rows {
+            let (id, source) = row?;
+            let rule = match parse_rule(source.as_str()) {
+                Ok(rule) => rule,
+                Err(err) => {
+                    eprintln!(
+                        "DatabaseError: parse rule id: {} in guild id: {} due to: {}",
+                        id, guild_id, err
+                    );
+                    continue;
+                }
+            };
+            rules.push(GuildRule { id, source, rule });
+        }
+        Ok(rules)
+    }
+
+    pub fn add_rule(&self, guild_id: GuildId, source: &str) -> Result<i64> {
+        let conn = self.lock()?;
+        conn.execute(
+            "INSERT INTO rules (guild_id, source) VALUES (?1, ?2)",
+            params![guild_id.0 as i64, source],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn remove_rule(&self, guild_id: GuildId, id: i64) -> Result<bool> {
+        let removed = self.lock()?.execute(
+            "DELETE FROM rules WHERE guild_id = ?1 AND id = ?2",
+            params![guild_id.0 as i64, id],
+        )?;
+        Ok(removed > 0)
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use rusqlite::params;
+use serenity::model::id::GuildId;
+
+use crate::{
+    cache::players::{PlayerSplitsData, Players},
+    Result,
+};
+
+use super::Database;
+
+impl Database {
+    pub fn get_players(&self, guild_id: GuildId) -> Result<Players> {
+        let conn = self.lock()?;
+        let mut statement = conn.prepare(
+            "SELECT name, first_structure, second_structure, blind, eye_spy, end_enter, finish
+            FROM runners WHERE guild_id = ?1",
+        )?;
+        let rows = statement.query_map(params![guild_id.0 as i64], |row| {
+            let name: String = row.get(0)?;
+            let splits = PlayerSplitsData {
+                first_structure: row.get(1)?,
+                second_structure: row.get(2)?,
+                blind: row.get(3)?,
+                eye_spy: row.get(4)?,
+                end_enter: row.get(5)?,
+                finish: row.get(6)?,
+            };
+            Ok((name, splits))
+        })?;
+        let mut players = Players::new();
+        for row in rows {
+            let (name, splits) = row?;
+            players.insert(name.to_lowercase(), splits);
+        }
+        Ok(players)
+    }
+
+    // Maps the uuid of every runner that is linked to an account to their lowercase name.
+    pub fn get_runner_uuids(&self, guild_id: GuildId) -> Result<HashMap<String, String>> {
+        let conn = self.lock()?;
+        let mut statement = conn
+            .prepare("SELECT uuid, name FROM runners WHERE guild_id = ?1 AND uuid IS NOT NULL")?;
+        let rows = statement.query_map(params![guild_id.0 as i64], |row| {
+            let uuid: String = row.get(0)?;
+            let name: String = row.get(1)?;
+            Ok((uuid, name))
+        })?;
+        let mut runner_uuids = HashMap::new();
+        for row in rows {
+            let (uuid, name) = row?;
+            runner_uuids.insert(uuid, name.to_lowercase());
+        }
+        Ok(runner_uuids)
+    }
+
+    // A runner that is added again under a new name keeps their entry, since the uuid
+    // is the same.
+    pub fn upsert_player(
+        &self,
+        guild_id: GuildId,
+        name: &str,
+        uuid: Option<&str>,
+        splits: &PlayerSplitsData,
+    ) -> Result<()> {
+        if let Some(uuid) = uuid {
+            self.rename_player(guild_id, uuid, name)?;
+        }
+        self.lock()?.execute(
+            "INSERT INTO runners
+                (guild_id, name, first_structure, second_structure, blind, eye_spy, end_enter, finish, uuid)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            ON CONFLICT (guild_id, name) DO UPDATE SET
+                name = excluded.name,
+                first_structure = excluded.first_structure,
+                second_structure = excluded.second_structure,
+                blind = excluded.blind,
+                eye_spy = excluded.eye_spy,
+                end_enter = excluded.end_enter,
+                finish = excluded.finish,
+                uuid = COALESCE(excluded.uuid, uuid)",
+            params![
+                guild_id.0 as i64,
+                name,
+                splits.first_structure,
+                splits.second_structure,
+                splits.blind,
+                splits.eye_spy,
+                splits.end_enter,
+                splits.finish,
+                uuid,
+            ],
+        )?;
+        Ok(())
+    }
+
+    // Names are unique in Minecraft, so any other entry with the new name is outdated
+    // and replaced.
+    pub fn rename_player(&self, guild_id: GuildId, uuid: &str, name: &str) -> Result<bool> {
+        let conn = self.lock()?;
+        conn.execute(
+            "DELETE FROM runners WHERE guild_id = ?1 AND name = ?2 AND uuid IS NOT ?3
+            AND EXISTS (SELECT 1 FROM runners WHERE guild_id = ?1 AND uuid = ?3)",
+            params![guild_id.0 as i64, name, uuid],
+        )?;
+        let renamed = conn.execute(
+            "UPDATE runners SET name = ?2 WHERE guild_id = ?1 AND uuid = ?3",
+            params![guild_id.0 as i64, name, uuid],
+        )?;
+        Ok(renamed > 0)
+    }
+
+    // Links a runner that was added by name to their account.
+    pub fn link_player(&self, guild_id: GuildId, name: &str, uuid: &str) -> Result<bool> {
+        let linked = self.lock()?.execute(
+            "UPDATE runners SET uuid = ?3 WHERE guild_id = ?1 AND name = ?2 AND uuid IS NULL
+            AND NOT EXISTS (SELECT 1 FROM runners WHERE guild_id = ?1 AND uuid = ?3)",
+            params![guild_id.0 as i64, name, uuid],
+        )?;
+        Ok(linked > 0)
+    }
+
+    pub fn remove_player(&self, guild_id: GuildId, name: &str) -> Result<bool> {
+        let removed = self.lock()?.execute(
+            "DELETE FROM runners WHERE guild_id = ?1 AND name = ?2",
+            params![guild_id.0 as i64, name],
+        )?;
+        Ok(removed > 0)
+    }
+}
@@ -0,0 +1,37 @@
+use rusqlite::{params, OptionalExtension};
+use serenity::model::id::GuildId;
+
+use crate::Result;
+
+use super::Database;
+
+impl Database {
+    pub fn get_setting(&self, guild_id: GuildId, key: &str) -> Result<Option<String>> {
+        let value = self
+            .lock()?
+            .query_row(
+                "SELECT value FROM settings WHERE guild_id = ?1 AND key = ?2",
+                params![guild_id.0 as i64, key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(value)
+    }
+
+    pub fn set_setting(&self, guild_id: GuildId, key: &str, value: &str) -> Result<()> {
+        self.lock()?.execute(
+            "INSERT INTO settings (guild_id, key, value) VALUES (?1, ?2, ?3)
+            ON CONFLICT (guild_id, key) DO UPDATE SET value = excluded.value",
+            params![guild_id.0 as i64, key, value],
+        )?;
+        Ok(())
+    }
+
+    pub fn remove_setting(&self, guild_id: GuildId, key: &str) -> Result<()> {
+        self.lock()?.execute(
+            "DELETE FROM settings WHERE guild_id = ?1 AND key = ?2",
+            params![guild_id.0 as i64, key],
+        )?;
+        Ok(())
+    }
+}
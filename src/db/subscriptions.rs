@@ -0,0 +1,79 @@
+use rusqlite::{params, OptionalExtension};
+use serenity::model::id::{GuildId, UserId};
+
+use crate::{
+    cache::{split::Split, subscription::Subscription},
+    Result,
+};
+
+use super::Database;
+
+impl Database {
+    pub fn get_subscriptions(&self, guild_id: GuildId) -> Result<Vec<Subscription>> {
+        let conn = self.lock()?;
+        let mut statement = conn.prepare(
+            "SELECT id, user_id, runner, split, time FROM subscriptions
+            WHERE guild_id = ?1 ORDER BY id",
+        )?;
+        let rows = statement.query_map(params![guild_id.0 as i64], |row| {
+            let id: i64 = row.get(0)?;
+            let user_id: i64 = row.get(1)?;
+            let runner: String = row.get(2)?;
+            let split: String = row.get(3)?;
+            let time: u64 = row.get(4)?;
+            Ok((id, user_id, runner, split, time))
+        })?;
+        let mut subscriptions = vec![];
+        for row in rows {
+            let (id, user_id, runner, split, time) = row?;
+            let split = match Split::from_str(split.as_str()) {
+                Some(split) => split,
+                None => {
+                    eprintln!(
+                        "DatabaseError: parse subscription split: '{}' in guild id: {}",
+                        split, guild_id
+                    );
+                    continue;
+                }
+            };
+            subscriptions.push(Subscription {
+                id,
+                user_id: UserId(user_id as u64),
+                runner,
+                split,
+                time,
+            });
+        }
+        Ok(subscriptions)
+    }
+
+    pub fn add_subscription(&self, guild_id: GuildId, subscription: &Subscription) -> Result<i64> {
+        let conn = self.lock()?;
+        conn.execute(
+            "INSERT INTO subscriptions (guild_id, user_id, runner, split, time)
+            VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                guild_id.0 as i64,
+                subscription.user_id.0 as i64,
+                subscription.runner,
+                subscription.split.to_str(),
+                subscription.time,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    // Subscriptions can only be removed by the user that owns them. The guild of the
+    // removed subscription is returned so that its cache can be updated.
+    pub fn remove_subscription(&self, user_id: UserId, id: i64) -> Result<Option<GuildId>> {
+        let conn = self.lock()?;
+        let guild_id: Option<i64> = conn
+            .query_row(
+                "DELETE FROM subscriptions WHERE id = ?1 AND user_id = ?2 RETURNING guild_id",
+                params![id, user_id.0 as i64],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(guild_id.map(|guild_id| GuildId(guild_id as u64)))
+    }
+}
@@ -1,3 +1,5 @@
 pub const SPECIAL_UNDERSCORE: &'static str = "ˍ";
 pub const PEARL_EMOJI: &'static str = "<:enderpearl:1249639829252345916>";
 pub const ROD_EMOJI: &'static str = "<:blazerod:1249633180378464381>";
+pub const DISPATCH_CONCURRENCY: usize = 16;
+pub const DISPATCH_QUEUE_SIZE: usize = 64;
@@ -0,0 +1,65 @@
+use serenity::{
+    builder::CreateEmbedAuthor, client::Context, model::application::component::ButtonStyle,
+    model::id::UserId,
+};
+
+pub struct DirectMessage {
+    pub user_id: UserId,
+    pub subscription_id: i64,
+    pub guild_name: String,
+    pub runner_name: String,
+    pub split_desc: String,
+    pub author: CreateEmbedAuthor,
+    pub fields: Vec<(String, String, bool)>,
+}
+
+impl DirectMessage {
+    pub async fn send(&self, ctx: &Context) -> bool {
+        let channel = match self.user_id.create_dm_channel(&ctx.http).await {
+            Ok(channel) => channel,
+            Err(err) => {
+                eprintln!(
+                    "DirectMessageError: open dm for user id: {} due to: {}",
+                    self.user_id, err
+                );
+                return false;
+            }
+        };
+        match channel
+            .send_message(&ctx.http, |m| {
+                m.embed(|e| {
+                    e.set_author(self.author.clone());
+                    for (name, value, inline) in self.fields.iter() {
+                        e.field(name, value, *inline);
+                    }
+                    e.footer(|f| f.text(format!("Subscribed in {}", self.guild_name)))
+                })
+                .components(|c| {
+                    c.create_action_row(|r| {
+                        r.create_button(|b| {
+                            b.custom_id(format!("unsubscribe:{}", self.subscription_id))
+                                .label("Unsubscribe")
+                                .style(ButtonStyle::Secondary)
+                        })
+                    })
+                })
+            })
+            .await
+        {
+            Ok(_) => {
+                println!(
+                    "Sent dm to user id: {} for user with name: '{}' for split: '{}'.",
+                    self.user_id, self.runner_name, self.split_desc
+                );
+                true
+            }
+            Err(err) => {
+                eprintln!(
+                    "DirectMessageError: send dm to user id: {} due to: {}",
+                    self.user_id, err
+                );
+                false
+            }
+        }
+    }
+}
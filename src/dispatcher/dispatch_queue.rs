@@ -0,0 +1,63 @@
+use std::{collections::HashMap, sync::Arc};
+
+use serenity::client::Context;
+use tokio::{sync::Semaphore, task::JoinHandle};
+
+use crate::{cache::CacheManager, handler::ArcMutex, ws::response::Response};
+
+use super::{consts::DISPATCH_QUEUE_SIZE, Dispatcher};
+
+// Dispatches responses in the background, so reading the next response never waits on
+// a slow guild. Responses of the same run are still dispatched in the order they came
+// in, and at most DISPATCH_QUEUE_SIZE responses are in flight at once.
+pub struct DispatchQueue {
+    ctx: Arc<Context>,
+    cache_manager: ArcMutex<CacheManager>,
+    permits: Arc<Semaphore>,
+    runs: HashMap<String, JoinHandle<()>>,
+}
+
+impl DispatchQueue {
+    pub fn new(ctx: Arc<Context>, cache_manager: ArcMutex<CacheManager>) -> Self {
+        Self {
+            ctx,
+            cache_manager,
+            permits: Arc::new(Semaphore::new(DISPATCH_QUEUE_SIZE)),
+            runs: HashMap::new(),
+        }
+    }
+
+    pub async fn push(&mut self, response: Response) {
+        self.runs.retain(|_, handle| !handle.is_finished());
+        // Waits for a free slot when the queue is full.
+        let permit = match self.permits.clone().acquire_owned().await {
+            Ok(permit) => permit,
+            Err(err) => return eprintln!("DispatchQueueError: get permit due to: {}", err),
+        };
+        let previous = self.runs.remove(&response.world_id);
+        let world_id = response.world_id.to_owned();
+        let dispatcher = Dispatcher {
+            ctx: self.ctx.clone(),
+            response,
+            cache_manager: self.cache_manager.clone(),
+        };
+        let handle = tokio::spawn(async move {
+            if let Some(previous) = previous {
+                previous.await.ok();
+            }
+            match dispatcher.dispatch().await {
+                Ok(_) => (),
+                Err(err) => eprintln!("Dispatch error: {}", err),
+            };
+            drop(permit);
+        });
+        self.runs.insert(world_id, handle);
+    }
+
+    // Waits until every queued response is dispatched.
+    pub async fn flush(&mut self) {
+        for (_, handle) in self.runs.drain() {
+            handle.await.ok();
+        }
+    }
+}
@@ -1,10 +1,28 @@
-use serenity::builder::CreateEmbedAuthor;
+use std::collections::HashMap;
 
-use crate::{utils::get_event_type::get_event_type, ws::response::EventType, Result};
+use serenity::{
+    builder::CreateEmbedAuthor,
+    futures::{stream, StreamExt},
+    model::id::GuildId,
+};
+
+use crate::{
+    cache::{runs::RunMessage, split::Split, CacheManager},
+    utils::{format_time::format_time, get_event_type::get_event_type},
+    ws::response::{Event, EventType},
+    Result,
+};
 
 use super::{
-    consts::SPECIAL_UNDERSCORE, non_pace_event::handle_non_pace_event,
-    pace_event::handle_pace_event, Dispatcher,
+    consts::{DISPATCH_CONCURRENCY, SPECIAL_UNDERSCORE},
+    direct_message::DirectMessage,
+    get_run_info::get_run_info,
+    leaderboard_update::get_leaderboard_update,
+    non_pace_event::handle_non_pace_event,
+    pace_event::handle_pace_event,
+    reset_event::handle_reset_event,
+    runner_rename::handle_runner_rename,
+    Dispatcher, GuildUpdate,
 };
 
 impl Dispatcher {
@@ -14,18 +32,184 @@ impl Dispatcher {
             println!("Skipping record because it was not of 1.16.1.");
             return Ok(());
         }
-        let last_event = match self.response.event_list.last() {
-            Some(evt) => evt,
-            None => {
-                return Err(format!(
-                    "DispatcherError: get last event from events list for response: {:#?}",
-                    self.response
-                )
-                .into())
+        if self.response.event_list.is_empty() {
+            return Err(format!(
+                "DispatcherError: get events from events list for response: {:#?}",
+                self.response
+            )
+            .into());
+        }
+        // Routing only needs the cache, so the lock is released before any of the
+        // Discord calls are made.
+        let guild_updates = {
+            let mut locked_cache_manager = self.cache_manager.lock().await;
+            self.get_guild_updates(&mut locked_cache_manager)
+        };
+        stream::iter(guild_updates)
+            .for_each_concurrent(DISPATCH_CONCURRENCY, |(guild_id, updates)| async move {
+                // Updates for the same guild are kept in order.
+                for update in updates {
+                    self.send_update(guild_id, update).await;
+                }
+            })
+            .await;
+        Ok(())
+    }
+
+    async fn send_update(&self, guild_id: GuildId, update: GuildUpdate) {
+        let message = match update {
+            GuildUpdate::Message(message) => message,
+            GuildUpdate::RunEnd(run_end) => {
+                let ended = run_end.send(&self.ctx).await;
+                let mut locked_cache_manager = self.cache_manager.lock().await;
+                return locked_cache_manager.untrack_ended_messages(
+                    &self.response.world_id,
+                    guild_id,
+                    &ended,
+                );
+            }
+            GuildUpdate::DirectMessage(direct_message) => {
+                if direct_message.send(&self.ctx).await {
+                    let mut locked_cache_manager = self.cache_manager.lock().await;
+                    locked_cache_manager.record_dm(direct_message.user_id);
+                }
+                return;
+            }
+            GuildUpdate::Leaderboard(leaderboard) => {
+                return leaderboard.send(&self.ctx, &self.cache_manager).await
+            }
+            GuildUpdate::RunnerRename(runner_rename) => {
+                return runner_rename.send(&self.ctx, guild_id).await
             }
         };
-        let mut locked_guild_cache = self.cache_manager.lock().await;
-        for (_, guild_data) in locked_guild_cache.cache.iter_mut() {
+        let sent = message.send(&self.ctx).await;
+        if sent.is_empty() {
+            return;
+        }
+        let mut locked_cache_manager = self.cache_manager.lock().await;
+        for sent_message in sent {
+            locked_cache_manager.track_sent_message(
+                &self.response.world_id,
+                guild_id,
+                sent_message,
+                message.evolving.is_some(),
+            );
+        }
+    }
+
+    fn get_guild_updates(
+        &self,
+        cache_manager: &mut CacheManager,
+    ) -> HashMap<GuildId, Vec<GuildUpdate>> {
+        let mut guild_updates = HashMap::new();
+        let world_id = &self.response.world_id;
+        cache_manager.remove_expired_runs();
+        let new_events = self
+            .response
+            .event_list
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, evt)| {
+                let event_type = get_event_type(evt)?;
+                // Splits are only announced the first time they are reached, but every
+                // reset ends the run that came before it.
+                let occurrence = match event_type {
+                    EventType::Reset => self.response.event_list[..idx]
+                        .iter()
+                        .filter(|earlier| earlier.event_id == evt.event_id)
+                        .count(),
+                    _ => 0,
+                };
+                Some((evt, occurrence, event_type))
+            })
+            .filter(|(evt, occurrence, _)| {
+                !cache_manager.is_dispatched(world_id, &evt.event_id, *occurrence)
+            })
+            .collect::<Vec<_>>();
+        if new_events.is_empty() {
+            println!(
+                "Skipping world id: '{}' because it has no new events to dispatch.",
+                world_id
+            );
+            return guild_updates;
+        }
+        for (event, occurrence, event_type) in new_events {
+            cache_manager.mark_dispatched(world_id, &event.event_id, occurrence);
+            for (guild_id, update) in self.dispatch_event(event, event_type, cache_manager) {
+                let updates = guild_updates.entry(guild_id).or_insert_with(Vec::new);
+                // The run message is only known once it has been sent, so later splits
+                // of the same run are folded into the update that sends it.
+                let update = match update {
+                    GuildUpdate::Message(message) => {
+                        match updates.iter_mut().find_map(|update| match update {
+                            GuildUpdate::Message(earlier) if earlier.continues(&message) => {
+                                Some(earlier)
+                            }
+                            _ => None,
+                        }) {
+                            Some(earlier) => {
+                                earlier.merge(message);
+                                continue;
+                            }
+                            None => GuildUpdate::Message(message),
+                        }
+                    }
+                    update => update,
+                };
+                updates.push(update);
+            }
+        }
+        guild_updates
+    }
+
+    fn dispatch_event(
+        &self,
+        last_event: &Event,
+        event_type: EventType,
+        cache_manager: &mut CacheManager,
+    ) -> Vec<(GuildId, GuildUpdate)> {
+        let mut updates = vec![];
+        let mut direct_messages: Vec<(GuildId, DirectMessage)> = vec![];
+        let event_split = match event_type {
+            EventType::PaceEvent => get_run_info(&self.response, last_event)
+                .and_then(|info| Some((info.split.desc(&info.structure)?, info.split))),
+            EventType::NonPaceEvent => Some(("Finish".to_string(), Split::Finish)),
+            EventType::Reset => None,
+        };
+        let mut run_data = cache_manager.runs.get_mut(&self.response.world_id);
+        for (guild_id, guild_data) in cache_manager.cache.iter_mut() {
+            let runner_rename =
+                handle_runner_rename(&cache_manager.db, *guild_id, guild_data, &self.response);
+            updates
+                .extend(runner_rename.map(|rename| (*guild_id, GuildUpdate::RunnerRename(rename))));
+            if let EventType::Reset = event_type {
+                if let Some(run_data) = &mut run_data {
+                    let run_end = handle_reset_event(
+                        &self.response,
+                        last_event,
+                        *guild_id,
+                        guild_data,
+                        run_data,
+                    );
+                    updates
+                        .extend(run_end.map(|run_end| (*guild_id, GuildUpdate::RunEnd(run_end))));
+                }
+                continue;
+            }
+            if let Some((_, split)) = &event_split {
+                let leaderboard = get_leaderboard_update(
+                    &cache_manager.db,
+                    *guild_id,
+                    guild_data,
+                    &self.response,
+                    last_event,
+                    split,
+                );
+                updates.extend(
+                    leaderboard
+                        .map(|leaderboard| (*guild_id, GuildUpdate::Leaderboard(leaderboard))),
+                );
+            }
             let live_link = match self.response.user.live_account.to_owned() {
                 Some(acc) => format!("https://twitch.tv/{}", acc),
                 None => {
@@ -40,7 +224,6 @@ impl Dispatcher {
                 }
             };
 
-            let stats_link = format!("https://paceman.gg/stats/run/{}", self.response.world_id);
             let mc_head_url = format!("https://mc-heads.net/avatar/{}", self.response.user.uuid);
             let author_name = self.response.nickname.replace("_", SPECIAL_UNDERSCORE);
             let mut author = CreateEmbedAuthor::default();
@@ -55,43 +238,120 @@ impl Dispatcher {
                 String::from("")
             };
 
-            let event_type = match get_event_type(&last_event) {
-                Some(etype) => etype,
-                None => {
-                    return Err(format!(
-                        "DispatcherError: get event type for event: {:#?}. Skipping all guilds.",
-                        last_event.event_id,
-                    )
-                    .into());
+            if let Some((split_desc, split)) = &event_split {
+                for subscription in guild_data.subscriptions.iter().filter(|subscription| {
+                    subscription.matches(&self.response.nickname, split, last_event.igt as u64)
+                }) {
+                    // Members that subscribed in several guilds only get one DM.
+                    if direct_messages
+                        .iter()
+                        .any(|(_, dm)| dm.user_id == subscription.user_id)
+                    {
+                        continue;
+                    }
+                    direct_messages.push((
+                        *guild_id,
+                        DirectMessage {
+                            user_id: subscription.user_id,
+                            subscription_id: subscription.id,
+                            guild_name: guild_data.name.to_owned(),
+                            runner_name: self.response.nickname.to_owned(),
+                            split_desc: split_desc.to_owned(),
+                            author: author.clone(),
+                            fields: vec![
+                                (
+                                    format!(
+                                        "{} - {}",
+                                        format_time(last_event.igt as u64),
+                                        split_desc
+                                    ),
+                                    String::new(),
+                                    false,
+                                ),
+                                (
+                                    "Splits".to_string(),
+                                    format!(
+                                        "[Link](https://paceman.gg/stats/run/{})",
+                                        self.response.world_id
+                                    ),
+                                    false,
+                                ),
+                            ],
+                        },
+                    ));
                 }
+            }
+
+            // Each rule pings at most once per run, on the first event it matches.
+            let matched_rules = match &run_data {
+                Some(run_data) => guild_data
+                    .rules
+                    .iter()
+                    .filter(|guild_rule| {
+                        !run_data
+                            .fired_rules
+                            .get(guild_id)
+                            .is_some_and(|fired| fired.contains(&guild_rule.id))
+                    })
+                    .filter(|guild_rule| guild_rule.rule.matches(&self.response, last_event))
+                    .map(|guild_rule| (guild_rule.id, guild_rule.rule.role_id))
+                    .collect::<Vec<_>>(),
+                None => vec![],
             };
-            match event_type {
-                EventType::NonPaceEvent => {
-                    handle_non_pace_event(
-                        self.ctx.clone(),
-                        &self.response,
-                        stats_link,
-                        author,
-                        live_indicator,
-                        last_event,
-                        guild_data,
-                    )
-                    .await;
-                }
-                EventType::PaceEvent => {
-                    handle_pace_event(
-                        self.ctx.clone(),
-                        &self.response,
-                        stats_link,
-                        author,
-                        live_indicator,
-                        last_event,
-                        guild_data,
-                    )
-                    .await;
-                }
+            let rule_roles = matched_rules
+                .iter()
+                .map(|(_, role_id)| *role_id)
+                .collect::<Vec<_>>();
+
+            let run_message = match &mut run_data {
+                Some(run_data) if guild_data.evolving_messages => Some(
+                    run_data
+                        .messages
+                        .entry(*guild_id)
+                        .or_insert_with(|| RunMessage::new(guild_data.pace_channel)),
+                ),
+                _ => None,
+            };
+            let message = match event_type {
+                EventType::NonPaceEvent => handle_non_pace_event(
+                    &self.response,
+                    author,
+                    live_indicator,
+                    last_event,
+                    guild_data,
+                    run_message,
+                    &rule_roles,
+                ),
+                EventType::PaceEvent => handle_pace_event(
+                    &self.response,
+                    author,
+                    live_indicator,
+                    last_event,
+                    guild_data,
+                    run_message,
+                    &rule_roles,
+                ),
+                EventType::Reset => None,
+            };
+            if let (Some(_), Some(run_data)) = (&message, &mut run_data) {
+                run_data
+                    .fired_rules
+                    .entry(*guild_id)
+                    .or_insert_with(Vec::new)
+                    .extend(matched_rules.iter().map(|(id, _)| *id));
             }
+            updates.extend(message.map(|message| (*guild_id, GuildUpdate::Message(message))));
         }
-        Ok(())
+        for (guild_id, direct_message) in direct_messages {
+            if !cache_manager.allow_dm(direct_message.user_id) {
+                println!(
+                    "Skipping dm to user id: {} because it was rate limited.",
+                    direct_message.user_id
+                );
+                continue;
+            }
+            updates.push((guild_id, GuildUpdate::DirectMessage(direct_message)));
+        }
+        updates
     }
 }
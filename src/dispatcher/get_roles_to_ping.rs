@@ -0,0 +1,73 @@
+use serenity::{model::id::RoleId, prelude::Mentionable};
+
+use crate::{
+    cache::{guild_data::GuildData, players::PlayerSplitsData, role_data::RoleData, split::Split},
+    utils::millis_to_mins_secs::millis_to_mins_secs,
+    ws::response::Response,
+};
+
+pub fn get_roles_to_ping<'a>(
+    guild_data: &'a GuildData,
+    player_data: &PlayerSplitsData,
+    split: &Split,
+    igt: u64,
+    response: &Response,
+) -> Vec<&'a RoleData> {
+    let (split_minutes, split_seconds) = millis_to_mins_secs(igt);
+    guild_data
+        .roles
+        .iter()
+        .filter(|role| {
+            if role.split != *split {
+                return false;
+            }
+            if role.guild_role.name.contains("PB") {
+                if !guild_data.is_private {
+                    return false;
+                }
+                match player_data.get(&role.split) {
+                    Some(pb_millis) => pb_millis > igt,
+                    None => false,
+                }
+            } else if role.guild_role.name.contains("+") {
+                guild_data.is_runner(&role.runner, &response.user.uuid, &response.nickname)
+                    && role.minutes >= split_minutes
+                    && (role.minutes != split_minutes || role.seconds > split_seconds)
+            } else {
+                role.minutes >= split_minutes
+                    && (role.minutes != split_minutes || role.seconds > split_seconds)
+            }
+        })
+        .filter(|role| {
+            guild_data
+                .item_requirements
+                .iter()
+                .filter(|requirement| requirement.applies_to(role))
+                .all(|requirement| requirement.is_met(response.item_data.as_ref()))
+        })
+        .collect::<Vec<_>>()
+}
+
+// Rule roles can overlap with the regular pace-roles, so each role is only mentioned once.
+pub fn get_ping_content(roles_to_ping: &[&RoleData], rule_roles: &[RoleId]) -> Option<String> {
+    let mut role_ids = roles_to_ping
+        .iter()
+        .map(|role| role.guild_role.id)
+        .collect::<Vec<_>>();
+    for role_id in rule_roles {
+        if !role_ids.contains(role_id) {
+            role_ids.push(*role_id);
+        }
+    }
+    if role_ids.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "-# {}",
+        role_ids
+            .iter()
+            .map(|role_id| role_id.mention().to_string())
+            .collect::<Vec<_>>()
+            .join(" "),
+    ))
+}
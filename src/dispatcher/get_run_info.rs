@@ -6,15 +6,24 @@ use crate::{
 use super::run_info::{RunInfo, RunType};
 
 pub fn get_run_info(response: &Response, last_event: &Event) -> Option<RunInfo> {
+    // Only look at what had happened by the time of this event, since a single
+    // response can carry several splits that are dispatched one after another.
+    let event_list = match response.event_list.iter().position(|evt| evt == last_event) {
+        Some(idx) => &response.event_list[..=idx],
+        None => &response.event_list[..],
+    };
+    let context_event_list = response
+        .context_event_list
+        .iter()
+        .filter(|ctx| ctx.igt <= last_event.igt)
+        .collect::<Vec<_>>();
     match last_event.event_id {
         EventId::RsgEnterBastion => {
             let mut split = Split::FirstStructure;
-            let bastion_ss_check = response
-                .event_list
+            let bastion_ss_check = event_list
                 .iter()
                 .any(|ctx| ctx.event_id == EventId::RsgEnterFortress);
-            let bastion_ss_context_check = response
-                .context_event_list
+            let bastion_ss_context_check = context_event_list
                 .iter()
                 .any(|ctx| ctx.event_id == EventId::RsgObtainBlazeRod);
 
@@ -29,15 +38,14 @@ pub fn get_run_info(response: &Response, last_event: &Event) -> Option<RunInfo>
         }
         EventId::RsgEnterFortress => {
             let mut split = Split::FirstStructure;
-            let fort_ss_check = response
-                .event_list
+            let fort_ss_check = event_list
                 .iter()
                 .filter(|evt| evt != &last_event)
                 .any(|evt| evt.event_id == EventId::RsgEnterBastion);
 
             let mut fort_ss_context_check = false;
             let mut context_hits = 0;
-            for ctx in response.context_event_list.iter() {
+            for ctx in context_event_list.iter() {
                 let context_check = ctx.event_id == EventId::RsgObtainCryingObsidian
                     || ctx.event_id == EventId::RsgObtainObsidian
                     || ctx.event_id == EventId::RsgLootBastion;
@@ -60,8 +68,7 @@ pub fn get_run_info(response: &Response, last_event: &Event) -> Option<RunInfo>
         }
         EventId::RsgFirstPortal => {
             let mut run_type = RunType::Modern;
-            if response
-                .event_list
+            if event_list
                 .iter()
                 .all(|evt| evt.event_id != EventId::RsgEnterBastion)
             {
@@ -83,3 +90,20 @@ pub fn get_run_info(response: &Response, last_event: &Event) -> Option<RunInfo>
         }
     }
 }
+
+// Only the blind tells whether a run is bastionless, so runs are treated as modern
+// until then.
+pub fn get_run_type(response: &Response, last_event: &Event) -> RunType {
+    let event_list = match response.event_list.iter().position(|evt| evt == last_event) {
+        Some(idx) => &response.event_list[..=idx],
+        None => &response.event_list[..],
+    };
+    match event_list
+        .iter()
+        .find(|evt| evt.event_id == EventId::RsgFirstPortal)
+        .and_then(|evt| get_run_info(response, evt))
+    {
+        Some(run_info) => run_info.run_type,
+        None => RunType::Modern,
+    }
+}
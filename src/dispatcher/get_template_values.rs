@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use crate::{
+    utils::format_time::format_time,
+    ws::response::{Event, Item, Response},
+};
+
+use super::{
+    consts::{PEARL_EMOJI, ROD_EMOJI, SPECIAL_UNDERSCORE},
+    run_info::RunType,
+};
+
+fn format_items(response: &Response) -> String {
+    let data = match &response.item_data {
+        Some(data) => data,
+        None => return String::new(),
+    };
+    [
+        (Item::MinecraftBlazeRod, ROD_EMOJI),
+        (Item::MinecraftEnderPearl, PEARL_EMOJI),
+    ]
+    .iter()
+    .filter_map(|(item, emoji)| {
+        let count = data.estimated_counts.get(item)?;
+        Some(format!("{} {}", emoji, count))
+    })
+    .collect::<Vec<_>>()
+    .join("  ")
+}
+
+pub fn get_template_values(
+    response: &Response,
+    last_event: &Event,
+    split_desc: &str,
+    live_indicator: &str,
+    run_type: &RunType,
+) -> HashMap<&'static str, String> {
+    HashMap::from([
+        ("runner", response.nickname.replace("_", SPECIAL_UNDERSCORE)),
+        ("split", split_desc.to_string()),
+        ("igt", format_time(last_event.igt as u64)),
+        ("rta", format_time(last_event.rta as u64)),
+        ("items", format_items(response)),
+        (
+            "stats_link",
+            format!("https://paceman.gg/stats/run/{}", response.world_id),
+        ),
+        ("live", live_indicator.to_string()),
+        (
+            "run_type",
+            match run_type {
+                RunType::Bastionless => "Bastionless",
+                RunType::Modern => "Modern",
+            }
+            .to_string(),
+        ),
+        (
+            "bastionless",
+            match run_type {
+                RunType::Bastionless => "Yes",
+                RunType::Modern => "",
+            }
+            .to_string(),
+        ),
+        (
+            "timestamp",
+            ((response.last_updated / 1000) as u64).to_string(),
+        ),
+    ])
+}
@@ -0,0 +1,223 @@
+use serenity::{
+    builder::{CreateEmbed, CreateEmbedAuthor},
+    client::Context,
+    model::{
+        channel::Message,
+        id::{ChannelId, MessageId},
+    },
+};
+
+use std::time::Instant;
+
+use crate::cache::runs::SentMessage;
+
+pub struct EvolvingMessage {
+    pub channel_id: ChannelId,
+    pub message_id: Option<MessageId>,
+}
+
+pub struct GuildMessage {
+    pub guild_name: String,
+    pub channel_id: ChannelId,
+    pub runner_name: String,
+    pub split_desc: String,
+    pub author: CreateEmbedAuthor,
+    pub fields: Vec<(String, String, bool)>,
+    pub colour: Option<u32>,
+    pub ping_content: Option<String>,
+    pub removable_roles: Vec<String>,
+    pub evolving: Option<EvolvingMessage>,
+}
+
+impl GuildMessage {
+    pub fn continues(&self, other: &GuildMessage) -> bool {
+        match (&self.evolving, &other.evolving) {
+            (Some(evolving), Some(other)) => {
+                evolving.channel_id == other.channel_id && evolving.message_id == other.message_id
+            }
+            _ => false,
+        }
+    }
+
+    // Splits of a run that come in with the same response end up in a single run
+    // message, which pings every role that any of the splits would have pinged.
+    pub fn merge(&mut self, later: GuildMessage) {
+        self.ping_content = match (self.ping_content.take(), later.ping_content) {
+            (Some(content), Some(later_content)) => {
+                let mut mentions = content.split_whitespace().skip(1).collect::<Vec<_>>();
+                for mention in later_content.split_whitespace().skip(1) {
+                    if !mentions.contains(&mention) {
+                        mentions.push(mention);
+                    }
+                }
+                Some(format!("-# {}", mentions.join(" ")))
+            }
+            (content, later_content) => content.or(later_content),
+        };
+        for role in later.removable_roles {
+            if !self.removable_roles.contains(&role) {
+                self.removable_roles.push(role);
+            }
+        }
+        self.channel_id = later.channel_id;
+        self.split_desc = later.split_desc;
+        self.author = later.author;
+        self.fields = later.fields;
+        self.colour = later.colour;
+    }
+
+    fn create_embed(&self, e: &mut CreateEmbed) {
+        e.set_author(self.author.clone());
+        for (name, value, inline) in self.fields.iter() {
+            e.field(name, value, *inline);
+        }
+        if let Some(colour) = self.colour {
+            e.colour(colour);
+        }
+    }
+
+    fn sent_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        is_ping: bool,
+    ) -> SentMessage {
+        SentMessage {
+            channel_id,
+            message_id,
+            author: self.author.clone(),
+            fields: self.fields.clone(),
+            colour: self.colour,
+            is_ping,
+            sent_at: Instant::now(),
+        }
+    }
+
+    async fn send_message(&self, ctx: &Context) -> Option<SentMessage> {
+        let mut message = match self
+            .channel_id
+            .send_message(ctx, |m| {
+                m.embed(|e| {
+                    self.create_embed(e);
+                    e
+                });
+                if let Some(ping_content) = &self.ping_content {
+                    m.content(ping_content);
+                }
+                m
+            })
+            .await
+        {
+            Ok(message) => message,
+            Err(err) => {
+                eprintln!(
+                    "GuildMessageError: send split: '{}' in guild name: {} due to: {}",
+                    self.split_desc, self.guild_name, err
+                );
+                return None;
+            }
+        };
+        println!(
+            "Sent pace-ping for user with name: '{}' for split: '{}' in guild name: {}.",
+            self.runner_name, self.split_desc, self.guild_name
+        );
+        self.remove_runner_roles(ctx, &mut message).await;
+        Some(self.sent_message(self.channel_id, message.id, false))
+    }
+
+    async fn edit_run_message(
+        &self,
+        ctx: &Context,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    ) -> Vec<SentMessage> {
+        match channel_id
+            .edit_message(&ctx.http, message_id, |m| {
+                m.embed(|e| {
+                    self.create_embed(e);
+                    e
+                })
+            })
+            .await
+        {
+            Ok(_) => (),
+            Err(err) => {
+                eprintln!(
+                    "GuildMessageError: edit run message for split: '{}' in guild name: {} due to: {}",
+                    self.split_desc, self.guild_name, err
+                );
+                return vec![];
+            }
+        };
+        let mut sent = vec![self.sent_message(channel_id, message_id, false)];
+        println!(
+            "Updated run message for user with name: '{}' with split: '{}' in guild name: {}.",
+            self.runner_name, self.split_desc, self.guild_name
+        );
+        let ping_content = match &self.ping_content {
+            Some(content) => content,
+            None => return sent,
+        };
+        // The edit itself does not notify anyone, so the roles are pinged in a reply.
+        match channel_id
+            .send_message(ctx, |m| {
+                m.content(ping_content)
+                    .reference_message((channel_id, message_id))
+            })
+            .await
+        {
+            Ok(mut message) => {
+                self.remove_runner_roles(ctx, &mut message).await;
+                sent.push(self.sent_message(channel_id, message.id, true));
+            }
+            Err(err) => {
+                eprintln!(
+                    "GuildMessageError: send ping for split: '{}' in guild name: {} due to: {}",
+                    self.split_desc, self.guild_name, err
+                );
+            }
+        };
+        sent
+    }
+
+    // Runner specific roles are only meant to ping once, so they are removed from
+    // the message right after it has been sent.
+    async fn remove_runner_roles(&self, ctx: &Context, message: &mut Message) {
+        let ping_content = match &self.ping_content {
+            Some(content) => content,
+            None => return,
+        };
+        let mut new_content = ping_content.to_owned();
+        for role in self.removable_roles.iter() {
+            let replacable_str = format!("{} ", role);
+            new_content = new_content.replace(replacable_str.as_str(), "");
+        }
+        if &new_content == ping_content {
+            return;
+        }
+        match message.edit(&ctx.http, |m| m.content(new_content)).await {
+            Ok(_) => (),
+            Err(err) => {
+                eprintln!("GuildMessageError: edit message due to: {}", err);
+            }
+        };
+    }
+
+    pub async fn send(&self, ctx: &Context) -> Vec<SentMessage> {
+        let run_message = self
+            .evolving
+            .as_ref()
+            .and_then(|e| Some((e.channel_id, e.message_id?)));
+        let mut sent = match run_message {
+            Some((channel_id, message_id)) => {
+                self.edit_run_message(ctx, channel_id, message_id).await
+            }
+            None => vec![],
+        };
+        // A run message that can't be edited anymore is replaced by a new one.
+        if sent.is_empty() {
+            sent.extend(self.send_message(ctx).await);
+        }
+        sent
+    }
+}
@@ -0,0 +1,74 @@
+use serenity::{client::Context, model::id::GuildId};
+
+use crate::{
+    cache::{guild_data::GuildData, leaderboard::LeaderboardEntry, split::Split, CacheManager},
+    db::Database,
+    handler::ArcMutex,
+    utils::{format_uuid::format_uuid, update_leaderboard::refresh_leaderboard},
+    ws::response::{Event, Response},
+};
+
+pub struct LeaderboardUpdate {
+    pub guild_id: GuildId,
+    pub guild_name: String,
+}
+
+impl LeaderboardUpdate {
+    pub async fn send(&self, ctx: &Context, cache_manager: &ArcMutex<CacheManager>) {
+        match refresh_leaderboard(ctx, self.guild_id, cache_manager).await {
+            Ok(_) => {
+                println!("Updated leaderboard for guild name: {}.", self.guild_name);
+            }
+            Err(err) => {
+                eprintln!(
+                    "LeaderboardUpdateError: update leaderboard in guild name: {} due to: {}",
+                    self.guild_name, err
+                );
+            }
+        };
+    }
+}
+
+pub fn get_leaderboard_update(
+    db: &Database,
+    guild_id: GuildId,
+    guild_data: &GuildData,
+    response: &Response,
+    last_event: &Event,
+    split: &Split,
+) -> Option<LeaderboardUpdate> {
+    if guild_data.lb_channel.is_none() || !guild_data.is_private {
+        return None;
+    }
+    guild_data.find_player(&response.user.uuid, &response.nickname)?;
+    let entry = LeaderboardEntry {
+        uuid: format_uuid(&response.user.uuid),
+        runner: response.nickname.to_owned(),
+        split: split.to_owned(),
+        time: last_event.igt as u64,
+        world_id: Some(response.world_id.to_owned()),
+        date: response.last_updated / 1000,
+    };
+    // A new best of the current period can also change the board without being an
+    // all-time best.
+    let period = guild_data.leaderboard_period.as_ref();
+    let since = period
+        .and_then(|period| period.bounds(entry.date))
+        .map(|(start, _)| start);
+    match db.add_leaderboard_run(guild_id, &entry, since) {
+        Ok(true) => (),
+        Ok(false) => return None,
+        Err(err) => {
+            eprintln!(
+                "LeaderboardUpdateError: add run for runner name: {} in guild name: {} due to: {}",
+                response.nickname, guild_data.name, err
+            );
+            return None;
+        }
+    };
+    // The board itself is rendered when it is sent, so it is always up to date.
+    Some(LeaderboardUpdate {
+        guild_id,
+        guild_name: guild_data.name.to_owned(),
+    })
+}
@@ -1,15 +1,37 @@
 use serenity::client::Context;
 use std::sync::Arc;
 pub mod consts;
+pub mod direct_message;
+pub mod dispatch_queue;
 pub mod dispatcher;
+pub mod get_roles_to_ping;
 pub mod get_run_info;
+pub mod get_template_values;
+pub mod guild_message;
+pub mod leaderboard_update;
 pub mod non_pace_event;
 pub mod pace_event;
+pub mod reset_event;
+pub mod run_end;
 pub mod run_info;
+pub mod runner_rename;
 use crate::{cache::CacheManager, handler::ArcMutex, ws::response::Response};
 
+use self::{
+    direct_message::DirectMessage, guild_message::GuildMessage,
+    leaderboard_update::LeaderboardUpdate, run_end::RunEnd, runner_rename::RunnerRename,
+};
+
 pub struct Dispatcher {
     pub ctx: Arc<Context>,
     pub response: Response,
     pub cache_manager: ArcMutex<CacheManager>,
 }
+
+pub enum GuildUpdate {
+    Message(GuildMessage),
+    RunEnd(RunEnd),
+    DirectMessage(DirectMessage),
+    Leaderboard(LeaderboardUpdate),
+    RunnerRename(RunnerRename),
+}
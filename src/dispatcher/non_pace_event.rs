@@ -1,101 +1,119 @@
-use std::sync::Arc;
+use serenity::{builder::CreateEmbedAuthor, model::id::RoleId, prelude::Mentionable};
 
-use serenity::{builder::CreateEmbedAuthor, client::Context};
+use crate::{
+    cache::{
+        embed_template::EmbedTemplate, guild_data::GuildData, players::PlayerSplitsData,
+        runs::RunMessage, split::Split,
+    },
+    utils::millis_to_mins_secs::millis_to_mins_secs,
+    ws::response::{Event, Response},
+};
 
-use crate::{cache::guild_data::GuildData, utils::{format_time::format_time, millis_to_mins_secs::millis_to_mins_secs, update_leaderboard::update_leaderboard}, ws::response::{Event, Response}};
+use super::{
+    get_roles_to_ping::{get_ping_content, get_roles_to_ping},
+    get_run_info::get_run_type,
+    get_template_values::get_template_values,
+    guild_message::{EvolvingMessage, GuildMessage},
+};
 
-use super::consts::SPECIAL_UNDERSCORE;
-
-pub async fn handle_non_pace_event(ctx: Arc<Context>, response: &Response, stats_link: String, author: CreateEmbedAuthor, live_indicator: String, last_event: &Event, guild_data: &mut GuildData) {
-        let player_data = match guild_data.players.get_mut(&response.nickname.to_lowercase()) {
-            Some(data) => data,
-            None => {
-                return println!(
-                    "Skipping guild because player name: {} is not in the runners channel for guild name: {}", 
-                    response.nickname, 
-                    guild_data.name
+pub fn handle_non_pace_event(
+    response: &Response,
+    author: CreateEmbedAuthor,
+    live_indicator: String,
+    last_event: &Event,
+    guild_data: &mut GuildData,
+    mut run_message: Option<&mut RunMessage>,
+    rule_roles: &[RoleId],
+) -> Option<GuildMessage> {
+    let default_player_data = PlayerSplitsData::default();
+    let player_data = match guild_data.find_player(&response.user.uuid, &response.nickname) {
+        Some(data) => data,
+        None => {
+            if guild_data.is_private {
+                println!(
+                    "Skipping guild because player name: {} is not in the runners channel for guild name: {}",
+                    response.nickname, guild_data.name
                 );
+                return None;
             }
-        };
-
-        let runner_name = response.nickname.to_owned();
-        let (minutes, seconds) = millis_to_mins_secs(last_event.igt as u64);
-    
-        let finish_minutes = match player_data.finish {
-            Some(mins) => mins,
-            None => {
-                if !guild_data.is_private && minutes >= 10 {
-                    return println!(
-                        "Skipping guild name: {} because it is not a sub 10 completion and the guild is public.", 
-                        guild_data.name
-                    );
-                }
-                // minutes + 1 will always be greater than minutes. 
-                // This is done to send finish message always if finish time is not defined.
-                minutes + 1
-            }, 
-        };
-        if minutes >= finish_minutes {
-            return println!(
-                "Skipping guild name: {} because finish time is above the defined amount.",
-                guild_data.name,
-            )
+            &default_player_data
         }
+    };
 
-        let finish_content = format!(
-            "{} {} - Finish", 
-            live_indicator, 
-            format_time(last_event.igt as u64)
-        );
+    let runner_name = response.nickname.to_owned();
+    let (minutes, _) = millis_to_mins_secs(last_event.igt as u64);
 
-        match guild_data.pace_channel.send_message(&ctx, |m| {
-            m.embed(|e| {
-                e.set_author(author);
-                e.field(finish_content, "", true);
-                e.field("Splits", format!("[Link]({})", stats_link), false);
-                e.field("Time", format!("<t:{}:R>", (response.last_updated / 1000) as u64), false);
-                e
-            })
-        }).await {
-            Ok(_) => {
+    let finish_millis = match player_data.finish {
+        Some(millis) => millis,
+        None => {
+            if !guild_data.is_private && minutes >= 10 {
                 println!(
-                    "Sent pace-ping for user with name: '{}' for split: 'Finish' in guild name: {}.",
-                    response.nickname, guild_data.name 
-                );
-            }
-            Err(err) => {
-                return eprintln!(
-                    "HandleNonPaceEvent: send split: 'Finish' due to: {}",
-                    err
+                    "Skipping guild name: {} because it is not a sub 10 completion and the guild is public.",
+                    guild_data.name
                 );
+                return None;
             }
-        };
-
-        if !guild_data.is_private || guild_data.lb_channel.is_none() {
-            return println!(
-                "Can't handle non pace event for guild name: {} because it is a public server or does not have a leaderboard channel.", 
-                guild_data.name
-            );
+            // igt + 1 will always be greater than igt.
+            // This is done to send finish message always if finish time is not defined.
+            last_event.igt as u64 + 1
         }
+    };
+    if last_event.igt as u64 >= finish_millis {
+        println!(
+            "Skipping guild name: {} because finish time is above the defined amount.",
+            guild_data.name,
+        );
+        return None;
+    }
 
-        match update_leaderboard(&ctx, guild_data.lb_channel.unwrap(), runner_name.to_owned().replace("_", SPECIAL_UNDERSCORE), (minutes, seconds))
-            .await
-        {
-            Ok(_) => {
-                println!(
-                    "Updated leaderboard in #pacemanbot-runner-leaderboard for guild name: {}, runner name: {} with time: {}.", 
-                    guild_data.name, 
-                    runner_name, 
-                    format_time(last_event.igt as u64),
-                );
-            }
-            Err(err) => {
-                eprintln!(
-                    "HandleNonPaceEvent: update leaderboard in guild name: {} for runner name: {} due to: {}",
-                    guild_data.name,
-                    response.nickname.to_owned(), 
-                    err
-                );
-            }
-        };
+    let roles_to_ping = get_roles_to_ping(
+        guild_data,
+        player_data,
+        &Split::Finish,
+        last_event.igt as u64,
+        response,
+    );
+    let ping_content = get_ping_content(&roles_to_ping, rule_roles);
+    let removable_roles = roles_to_ping
+        .iter()
+        .filter(|r| r.runner.as_str() != "")
+        .map(|r| r.guild_role.mention().to_string())
+        .collect::<Vec<_>>();
+
+    let run_type = get_run_type(response, last_event);
+    let values = get_template_values(response, last_event, "Finish", &live_indicator, &run_type);
+    let template = &guild_data.embed_template;
+    let finish_content = EmbedTemplate::render(&template.finish_title, &values);
+
+    let fields = EmbedTemplate::render_fields(&template.finish_fields, &values);
+    let channel_id = guild_data.channel_for(&Split::Finish, &runner_name, &run_type);
+    if let Some(run_message) = &mut run_message {
+        run_message.move_to(channel_id);
+    }
+    let (fields, evolving) = match run_message {
+        Some(run_message) => (
+            run_message.add_split(finish_content, fields),
+            Some(EvolvingMessage {
+                channel_id: run_message.channel_id,
+                message_id: run_message.message_id,
+            }),
+        ),
+        None => (
+            [vec![(finish_content, String::new(), true)], fields].concat(),
+            None,
+        ),
+    };
+
+    Some(GuildMessage {
+        guild_name: guild_data.name.to_owned(),
+        channel_id,
+        runner_name,
+        split_desc: "Finish".to_string(),
+        author,
+        fields,
+        colour: template.colour(&Split::Finish),
+        ping_content,
+        removable_roles,
+        evolving,
+    })
 }
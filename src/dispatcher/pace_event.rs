@@ -1,183 +1,135 @@
-use std::sync::Arc;
+use serenity::{builder::CreateEmbedAuthor, model::id::RoleId, prelude::Mentionable};
 
-use serenity::{builder::CreateEmbedAuthor, client::Context, prelude::Mentionable};
+use crate::{
+    cache::{
+        embed_template::EmbedTemplate, guild_data::GuildData, players::PlayerSplitsData,
+        runs::RunMessage,
+    },
+    ws::response::{Event, Response},
+};
 
-use crate::{cache::{guild_data::GuildData, players::PlayerSplitsData}, utils::{format_time::format_time, millis_to_mins_secs::millis_to_mins_secs}, ws::response::{Event, Item, Response}};
+use super::{
+    get_roles_to_ping::{get_ping_content, get_roles_to_ping},
+    get_run_info::{get_run_info, get_run_type},
+    get_template_values::get_template_values,
+    guild_message::{EvolvingMessage, GuildMessage},
+};
 
-use super::{consts::{PEARL_EMOJI, ROD_EMOJI}, get_run_info::get_run_info, run_info::RunType};
-
-pub async fn handle_pace_event(ctx: Arc<Context>, response: &Response, stats_link: String, author: CreateEmbedAuthor, live_indicator: String, last_event: &Event, guild_data: &mut GuildData) 
-{
-        let run_info = 
-            match get_run_info(response, last_event) {
-                Some(info) => info,
-                None => {
-                    return eprintln!("HandlePaceEvent: Unrecognized event id: {:#?}.", last_event.event_id);
-                }
-            };
+pub fn handle_pace_event(
+    response: &Response,
+    author: CreateEmbedAuthor,
+    live_indicator: String,
+    last_event: &Event,
+    guild_data: &mut GuildData,
+    mut run_message: Option<&mut RunMessage>,
+    rule_roles: &[RoleId],
+) -> Option<GuildMessage> {
+    let run_info = match get_run_info(response, last_event) {
+        Some(info) => info,
+        None => {
+            eprintln!(
+                "HandlePaceEvent: Unrecognized event id: {:#?}.",
+                last_event.event_id
+            );
+            return None;
+        }
+    };
 
-        let player_data = match guild_data.players.get_mut(&response.nickname.to_lowercase()) {
-            Some(data) => data,
-            None => {
-                if guild_data.is_private {
-                    return println!(
-                        "Skipping guild because player name: {} is not in the runners channel for guild name: {}", 
-                        response.nickname, 
-                        guild_data.name
-                    );
-                }
-                let player_data = PlayerSplitsData::default();
-                guild_data.players.insert(response.nickname.to_owned().to_lowercase(), player_data);
-                guild_data.players.get_mut(&response.nickname.to_lowercase()).unwrap()
-            }
-        };
-        let split_desc = match run_info.split.desc(&run_info.structure) {
-            Some(desc) => desc,
-            None => {
-                return eprintln!("HandlePaceEvent: get split desc for split: {:#?}", run_info.split);
+    let default_player_data = PlayerSplitsData::default();
+    let player_data = match guild_data.find_player(&response.user.uuid, &response.nickname) {
+        Some(data) => data,
+        None => {
+            if guild_data.is_private {
+                println!(
+                    "Skipping guild because player name: {} is not in the runners channel for guild name: {}",
+                    response.nickname, guild_data.name
+                );
+                return None;
             }
-        };
-
-        let roles_to_ping = guild_data.roles
-            .iter()
-            .filter(|role| {
-                let (split_minutes, split_seconds) = millis_to_mins_secs(last_event.igt as u64);
-                if role.guild_role.name.contains("PB") {
-                    if !guild_data.is_private {
-                        return false;
-                    }
-                    let pb_minutes = player_data.get(&role.split).unwrap().to_owned();
-                    role.split == run_info.split && pb_minutes > split_minutes
-                } else if role.guild_role.name.contains("+") {
-                    role.split == run_info.split
-                        && role.runner.to_lowercase() == response.nickname.to_lowercase()
-                        && role.minutes >= split_minutes
-                        && (role.minutes != split_minutes || role.seconds > split_seconds)
-                } else {
-                    role.split == run_info.split
-                        && role.minutes >= split_minutes
-                        && (role.minutes != split_minutes || role.seconds > split_seconds)
-                }
-            })
-            .collect::<Vec<_>>();
-
-        if roles_to_ping.is_empty() {
-            return println!(
-                "Skipping split: '{}' because there are no roles to ping in guild name: {}.",
-                split_desc, guild_data.name 
+            &default_player_data
+        }
+    };
+    let split_desc = match run_info.split.desc(&run_info.structure) {
+        Some(desc) => desc,
+        None => {
+            eprintln!(
+                "HandlePaceEvent: get split desc for split: {:#?}",
+                run_info.split
             );
+            return None;
         }
+    };
 
-        let mut item_data_content = String::new();
+    let run_type = get_run_type(response, last_event);
+    let values = get_template_values(
+        response,
+        last_event,
+        &split_desc,
+        &live_indicator,
+        &run_type,
+    );
+    let template = &guild_data.embed_template;
+    let pace_content = EmbedTemplate::render(&template.pace_title, &values);
 
-        match &response.item_data {
-            Some(data) => {
-                let pearl_count = data.estimated_counts.get(&Item::MinecraftEnderPearl);
-                let rod_count = data.estimated_counts.get(&Item::MinecraftBlazeRod);
+    let roles_to_ping = get_roles_to_ping(
+        guild_data,
+        player_data,
+        &run_info.split,
+        last_event.igt as u64,
+        response,
+    );
 
-                if rod_count.is_some() {
-                    if item_data_content == "" {
-                        item_data_content = format!("{} {}", ROD_EMOJI, rod_count.unwrap());
-                    } else {
-                        item_data_content = format!("{}  {} {}", item_data_content, ROD_EMOJI, rod_count.unwrap());
-                    }
-                }
-                if pearl_count.is_some() {
-                    if item_data_content == "" {
-                        item_data_content = format!("{} {}", PEARL_EMOJI, pearl_count.unwrap());
-                    } else {
-                        item_data_content = format!("{}  {} {}", item_data_content, PEARL_EMOJI, pearl_count.unwrap());
-                    }
-                }
-                if item_data_content != "" {
-                    item_data_content = format!("\n{}", item_data_content); 
-                }
-            },
-            None => (),
-        }
+    let channel_id = guild_data.channel_for(&run_info.split, &response.nickname, &run_type);
+    if let Some(run_message) = &mut run_message {
+        run_message.move_to(channel_id);
+    }
 
-        let ping_content = format!(
-            "-# {}",
-            roles_to_ping
-                .iter()
-                .map(|role| role.guild_role.mention().to_string())
-                .collect::<Vec<_>>()
-                .join(" "),
+    // Runs that already have a message are still updated, just without a ping.
+    let run_message_id = run_message.as_ref().and_then(|rm| rm.message_id);
+    if roles_to_ping.is_empty() && rule_roles.is_empty() && run_message_id.is_none() {
+        if let Some(run_message) = run_message {
+            run_message.splits.push(pace_content);
+        }
+        println!(
+            "Skipping split: '{}' because there are no roles to ping in guild name: {}.",
+            split_desc, guild_data.name
         );
+        return None;
+    }
 
-        let pace_content = format!(
-            "{} {} - {}",
-            live_indicator,
-            format_time(last_event.igt as u64),
-            split_desc,
-        );
+    let mut fields = EmbedTemplate::render_fields(&template.pace_fields, &values);
 
-        match guild_data.pace_channel.send_message(
-            &ctx, 
-            |m| {
-                m.embed(|e| {
-                    e.set_author(author.clone());
-                    e.field(pace_content.clone(), "", true);
-                    e.field("Splits", format!("[Link]({})", stats_link.clone()), false);
-                    e.field("Time", format!("<t:{}:R>", (response.last_updated / 1000) as u64), false);
-                    if item_data_content != "" {
-                        e.field("Items", item_data_content.clone(), false);
-                    }
-                    if let RunType::Bastionless = run_info.run_type {
-                        e.field("Bastionless", "Yes", false);
-                    }
-                    e
-                })
-                .content(ping_content.to_owned())
-            }).await {
-            Ok(mut message) => {
-                println!(
-                    "Sent pace-ping for user with name: '{}' for split: '{}' in guild name: {}.",
-                    response.nickname, split_desc, guild_data.name 
-                );
-                let removable_roles = roles_to_ping
-                    .iter()
-                    .filter(|r| r.runner.as_str() != "")
-                    .map(|r| r.guild_role.mention())
-                    .collect::<Vec<_>>();
-                let mut new_content = ping_content.to_owned();
-                for role in removable_roles {
-                    let replacable_str = format!("{} ", role);
-                    new_content = new_content.replace(replacable_str.as_str(), "");
-                }
-                if new_content == ping_content {
-                    return;
-                }
-                match message.edit(
-                    &ctx.http, 
-                    |m| {
-                        m.embed(|e| {
-                            e.set_author(author);
-                            e.field(pace_content, "", true);
-                            e.field("Splits", format!("[Link]({})", stats_link), false);
-                            e.field("Time", format!("<t:{}:R>", (response.last_updated / 1000) as u64), false);
-                            if item_data_content != "" {
-                                e.field("Items", item_data_content, false);
-                            }
-                            if let RunType::Bastionless = run_info.run_type {
-                                e.field("Bastionless", "Yes", false);
-                            }
-                            e
-                        })
-                        .content(new_content)
-                    }).await {
-                    Ok(_) => (),
-                    Err(err) => {
-                        return eprintln!("HandlePaceEvent: edit message due to: {}", err);
-                    } 
-                };
-            }
-            Err(err) => {
-                return eprintln!(
-                    "HandlePaceEvent: send split: '{}' with roles: {:?} due to: {}",
-                    split_desc, roles_to_ping, err
-                );
-            }
-        };
-}
+    let (fields, evolving) = match run_message {
+        Some(run_message) => (
+            run_message.add_split(pace_content, fields),
+            Some(EvolvingMessage {
+                channel_id: run_message.channel_id,
+                message_id: run_message_id,
+            }),
+        ),
+        None => {
+            fields.insert(0, (pace_content, String::new(), true));
+            (fields, None)
+        }
+    };
+    let ping_content = get_ping_content(&roles_to_ping, rule_roles);
 
+    let removable_roles = roles_to_ping
+        .iter()
+        .filter(|r| r.runner.as_str() != "")
+        .map(|r| r.guild_role.mention().to_string())
+        .collect::<Vec<_>>();
+
+    Some(GuildMessage {
+        guild_name: guild_data.name.to_owned(),
+        channel_id,
+        runner_name: response.nickname.to_owned(),
+        split_desc,
+        author,
+        fields,
+        colour: template.colour(&run_info.split),
+        ping_content,
+        removable_roles,
+        evolving,
+    })
+}
@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use serenity::model::id::GuildId;
+
+use crate::{
+    cache::{guild_data::GuildData, runs::RunData},
+    utils::format_time::format_time,
+    ws::response::{Event, EventId, Response},
+};
+
+use super::{get_run_info::get_run_info, run_end::RunEnd};
+
+pub fn handle_reset_event(
+    response: &Response,
+    last_event: &Event,
+    guild_id: GuildId,
+    guild_data: &GuildData,
+    run_data: &RunData,
+) -> Option<RunEnd> {
+    if response
+        .event_list
+        .iter()
+        .any(|evt| evt.event_id == EventId::RsgCredits)
+    {
+        return None;
+    }
+    // The messages stay tracked until they have actually been ended.
+    let messages = run_data.sent.get(&guild_id)?.clone();
+
+    let reached_events = match response.event_list.iter().position(|evt| evt == last_event) {
+        Some(idx) => &response.event_list[..idx],
+        None => &response.event_list[..],
+    };
+    let final_split = reached_events.iter().rev().find_map(|evt| {
+        let run_info = get_run_info(response, evt)?;
+        Some((run_info.split.desc(&run_info.structure)?, evt.igt))
+    });
+    let ended_content = match final_split {
+        Some((split_desc, igt)) => {
+            format!("Reset after {} at {}.", split_desc, format_time(igt as u64))
+        }
+        None => "Reset.".to_string(),
+    };
+
+    Some(RunEnd {
+        guild_name: guild_data.name.to_owned(),
+        runner_name: response.nickname.to_owned(),
+        ended_field: ("Run ended".to_string(), ended_content, false),
+        messages,
+        ping_window: guild_data
+            .reset_ping_window
+            .map(|mins| Duration::from_secs(mins * 60)),
+    })
+}
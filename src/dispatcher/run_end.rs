@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use serenity::{client::Context, model::id::MessageId};
+
+use crate::cache::runs::SentMessage;
+
+pub struct RunEnd {
+    pub guild_name: String,
+    pub runner_name: String,
+    pub ended_field: (String, String, bool),
+    pub messages: Vec<SentMessage>,
+    pub ping_window: Option<Duration>,
+}
+
+impl RunEnd {
+    async fn mark_ended(&self, ctx: &Context, sent: &SentMessage) -> bool {
+        match sent
+            .channel_id
+            .edit_message(&ctx.http, sent.message_id, |m| {
+                m.embed(|e| {
+                    e.set_author(sent.author.clone());
+                    for (name, value, inline) in sent.fields.iter() {
+                        e.field(name, value, *inline);
+                    }
+                    if let Some(colour) = sent.colour {
+                        e.colour(colour);
+                    }
+                    let (name, value, inline) = &self.ended_field;
+                    e.field(name, value, *inline)
+                })
+            })
+            .await
+        {
+            Ok(_) => true,
+            Err(err) => {
+                eprintln!(
+                    "RunEndError: edit message id: {} in guild name: {} due to: {}",
+                    sent.message_id, self.guild_name, err
+                );
+                false
+            }
+        }
+    }
+
+    async fn delete_ping(&self, ctx: &Context, sent: &SentMessage) -> bool {
+        match sent
+            .channel_id
+            .delete_message(&ctx.http, sent.message_id)
+            .await
+        {
+            Ok(_) => true,
+            Err(err) => {
+                eprintln!(
+                    "RunEndError: delete ping id: {} in guild name: {} due to: {}",
+                    sent.message_id, self.guild_name, err
+                );
+                false
+            }
+        }
+    }
+
+    // Returns the messages that were dealt with, messages that failed are ended again
+    // on the next reset of the run.
+    pub async fn send(&self, ctx: &Context) -> Vec<MessageId> {
+        let mut ended = vec![];
+        for sent in self.messages.iter() {
+            let is_ended = match self.ping_window {
+                _ if !sent.is_ping => self.mark_ended(ctx, sent).await,
+                Some(window) if sent.sent_at.elapsed() >= window => {
+                    self.delete_ping(ctx, sent).await
+                }
+                _ => true,
+            };
+            if is_ended {
+                ended.push(sent.message_id);
+            }
+        }
+        println!(
+            "Marked run as ended for user with name: '{}' in guild name: {}.",
+            self.runner_name, self.guild_name
+        );
+        ended
+    }
+}
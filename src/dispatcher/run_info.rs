@@ -1,10 +1,29 @@
 use crate::cache::split::{Split, Structure};
 
+#[derive(PartialEq, Debug, Clone)]
 pub enum RunType {
     Bastionless,
     Modern,
 }
 
+impl RunType {
+    pub fn from_command_param(param: &str) -> Option<RunType> {
+        match param {
+            "bastionless" => Some(RunType::Bastionless),
+            "modern" => Some(RunType::Modern),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> String {
+        match self {
+            RunType::Bastionless => "bastionless",
+            RunType::Modern => "modern",
+        }
+        .to_string()
+    }
+}
+
 pub struct RunInfo {
     pub split: Split,
     pub structure: Option<Structure>,
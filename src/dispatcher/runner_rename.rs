@@ -0,0 +1,115 @@
+use serenity::{
+    client::Context,
+    model::id::{GuildId, RoleId},
+};
+
+use crate::{
+    cache::guild_data::GuildData, db::Database, utils::format_uuid::format_uuid,
+    ws::response::Response,
+};
+
+pub struct RunnerRename {
+    pub guild_name: String,
+    pub old_name: String,
+    pub new_name: String,
+    pub roles: Vec<(RoleId, String)>,
+}
+
+impl RunnerRename {
+    pub async fn send(&self, ctx: &Context, guild_id: GuildId) {
+        for (role_id, role_name) in self.roles.iter() {
+            match guild_id
+                .edit_role(&ctx.http, role_id, |r| r.name(role_name))
+                .await
+            {
+                Ok(_) => (),
+                Err(err) => eprintln!(
+                    "RunnerRenameError: rename role: '{}' in guild name: {} due to: {}",
+                    role_name, self.guild_name, err
+                ),
+            };
+        }
+        println!(
+            "Renamed runner: '{}' to: '{}' with {} roles in guild name: {}.",
+            self.old_name,
+            self.new_name,
+            self.roles.len(),
+            self.guild_name
+        );
+    }
+}
+
+// Keeps whitelisted runners attached to their account. Runners that were added by name
+// are linked on their first run, and linked runners that show up with a new name are
+// renamed together with their runner specific roles.
+pub fn handle_runner_rename(
+    db: &Database,
+    guild_id: GuildId,
+    guild_data: &mut GuildData,
+    response: &Response,
+) -> Option<RunnerRename> {
+    let uuid = format_uuid(&response.user.uuid);
+    let new_name = response.nickname.to_lowercase();
+    let old_name = match guild_data.runner_uuids.get(&uuid) {
+        Some(old_name) if old_name != &new_name => old_name.to_owned(),
+        Some(_) => return None,
+        None => {
+            if !guild_data.players.contains_key(&new_name)
+                || guild_data
+                    .runner_uuids
+                    .values()
+                    .any(|name| name == &new_name)
+            {
+                return None;
+            }
+            match db.link_player(guild_id, &response.nickname, &uuid) {
+                Ok(true) => {
+                    println!(
+                        "Linked runner: '{}' to uuid: {} in guild name: {}.",
+                        response.nickname, uuid, guild_data.name
+                    );
+                    guild_data.runner_uuids.insert(uuid, new_name);
+                }
+                Ok(false) => (),
+                Err(err) => eprintln!(
+                    "RunnerRenameError: link runner: '{}' in guild name: {} due to: {}",
+                    response.nickname, guild_data.name, err
+                ),
+            };
+            return None;
+        }
+    };
+    match db.rename_player(guild_id, &uuid, &response.nickname) {
+        Ok(true) => (),
+        Ok(false) => return None,
+        Err(err) => {
+            eprintln!(
+                "RunnerRenameError: rename runner: '{}' in guild name: {} due to: {}",
+                old_name, guild_data.name, err
+            );
+            return None;
+        }
+    };
+    if let Some(player_data) = guild_data.players.remove(&old_name) {
+        guild_data.players.insert(new_name.to_owned(), player_data);
+    }
+    guild_data.runner_uuids.retain(|_, name| name != &new_name);
+    guild_data.runner_uuids.insert(uuid, new_name);
+    let roles = guild_data
+        .roles
+        .iter_mut()
+        .filter(|role| role.runner.to_lowercase() == old_name)
+        .filter_map(|role| {
+            let (prefix, _) = role.guild_role.name.rsplit_once('+')?;
+            role.guild_role.name = format!("{}+{}", prefix, response.nickname);
+            role.runner = response.nickname.to_owned();
+            Some((role.guild_role.id, role.guild_role.name.to_owned()))
+        })
+        .collect::<Vec<_>>();
+    Some(RunnerRename {
+        guild_name: guild_data.name.to_owned(),
+        old_name,
+        new_name: response.nickname.to_owned(),
+        roles,
+    })
+}
@@ -3,18 +3,29 @@ use serenity::{
 };
 
 use crate::{
+    cache::CacheManager,
     components::application::{
-        migrate::migrate, send_role_selection_message::send_role_selection_message,
+        configure_channels::configure_channels, embed_template::embed_template,
+        evolving_messages::evolving_messages, item_requirements::item_requirements,
+        leaderboard::leaderboard, leaderboard_period::leaderboard_period, migrate::migrate,
+        reset_pings::reset_pings, routes::routes, rules::rules,
+        send_role_selection_message::send_role_selection_message,
         setup_default_roles::setup_default_roles, setup_pb_roles::setup_pb_roles,
-        setup_pings::setup_pings, setup_roles::setup_roles, validate_config::validate_config,
-        whitelist::whitelist,
+        setup_pings::setup_pings, setup_roles::setup_roles, subscribe::subscribe,
+        validate_config::validate_config, whitelist::whitelist,
     },
+    utils::send_audit_log::{is_audited, send_audit_log},
+    ws::connection_state::ConnectionState,
     Result,
 };
 
+use super::ArcMutex;
+
 pub async fn handle_application_command_interaction(
     ctx: &Context,
     command: &ApplicationCommandInteraction,
+    cache_manager: ArcMutex<CacheManager>,
+    connection_state: ArcMutex<ConnectionState>,
 ) -> Result<()> {
     let guild_id = match command.guild_id {
         Some(guild_id) => guild_id,
@@ -46,15 +57,28 @@ pub async fn handle_application_command_interaction(
             return Err(content.into());
         }
     };
+    let audit_cache_manager = cache_manager.clone();
     match match command.data.name.as_str() {
         "send_message" => send_role_selection_message(&ctx, &roles, command).await,
         "setup_default_roles" => setup_default_roles(&ctx, guild_id, command).await,
-        "setup_pings" => setup_pings(&ctx, guild_id, command).await,
+        "setup_pings" => setup_pings(&ctx, guild_id, command, cache_manager).await,
         "setup_roles" => setup_roles(&ctx, guild_id, command).await,
         "setup_pb_roles" => setup_pb_roles(&ctx, guild_id, command).await,
-        "whitelist" => whitelist(&ctx, guild_id, command).await,
-        "migrate" => migrate(&ctx, guild_id, command).await,
-        "validate_config" => validate_config(&ctx, guild_id, command).await,
+        "whitelist" => whitelist(&ctx, guild_id, command, cache_manager).await,
+        "migrate" => migrate(&ctx, guild_id, command, cache_manager).await,
+        "evolving_messages" => evolving_messages(ctx, guild_id, command, cache_manager).await,
+        "item_requirements" => item_requirements(ctx, guild_id, command, cache_manager).await,
+        "reset_pings" => reset_pings(ctx, guild_id, command, cache_manager).await,
+        "rules" => rules(ctx, guild_id, command, cache_manager).await,
+        "routes" => routes(ctx, guild_id, command, cache_manager).await,
+        "subscribe" => subscribe(ctx, guild_id, command, cache_manager).await,
+        "embed_template" => embed_template(ctx, guild_id, command, cache_manager).await,
+        "leaderboard" => leaderboard(ctx, guild_id, command, cache_manager).await,
+        "leaderboard_period" => leaderboard_period(ctx, guild_id, command, cache_manager).await,
+        "configure_channels" => configure_channels(ctx, guild_id, command, cache_manager).await,
+        "validate_config" => {
+            validate_config(&ctx, guild_id, command, cache_manager, connection_state).await
+        }
         _ => {
             return Err(format!(
                 "ApplicationCommandInteractionError: Unrecognized command: {}.",
@@ -72,5 +96,15 @@ pub async fn handle_application_command_interaction(
             return Err(content.into());
         }
     };
+    let action = command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == "action")
+        .and_then(|option| option.value.as_ref())
+        .and_then(|value| value.as_str());
+    if is_audited(command.data.name.as_str(), action) {
+        send_audit_log(ctx, guild_id, command, audit_cache_manager).await;
+    }
     Ok(())
 }
@@ -13,17 +13,27 @@ pub async fn handle_channel_events(
     guild_id: GuildId,
     cache_manager: ArcMutex<CacheManager>,
 ) {
-    match channel.name.as_str() {
-        "pacemanbot-runner-names" | "pacemanbot" | "pacemanbot-runner-leaderboard" => {
-            match handle_update_cache(ctx, guild_id, cache_manager).await {
-                Ok(_) => (),
-                Err(err) => eprintln!("ChannelEventsError: {}", err),
-            };
+    let is_bound_channel = match cache_manager.lock().await.cache.get(&guild_id) {
+        Some(guild_data) => {
+            guild_data.pace_channel == channel.id
+                || guild_data.finish_channel == channel.id
+                || guild_data.lb_channel == Some(channel.id)
+                || guild_data.config_channel == Some(channel.id)
+                || guild_data.audit_channel == Some(channel.id)
         }
+        None => false,
+    };
+    match channel.name.as_str() {
+        "pacemanbot-runner-names" | "pacemanbot" | "pacemanbot-runner-leaderboard" => (),
+        _ if is_bound_channel => (),
         _ => {
             return println!(
                 "Skipping channel event because it is not something that concerns the bot."
             )
         }
-    }
+    };
+    match handle_update_cache(ctx, guild_id, cache_manager).await {
+        Ok(_) => (),
+        Err(err) => eprintln!("ChannelEventsError: {}", err),
+    };
 }
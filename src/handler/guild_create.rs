@@ -5,6 +5,7 @@ use serenity::{
 
 use crate::{
     cache::CacheManager, components::application::default_commands::setup_default_commands,
+    utils::import_legacy_messages::import_legacy_messages,
 };
 
 use super::ArcMutex;
@@ -17,8 +18,14 @@ pub async fn handle_guild_create(
     setup_default_commands(&ctx, guild_id).await;
     ctx.set_presence(Some(Activity::watching("paceman.gg")), OnlineStatus::Online)
         .await;
+    if let Err(err) = import_legacy_messages(ctx, guild_id, &cache_manager)
+        .await
+        .map_err(|err| err.to_string())
+    {
+        return eprintln!("GuildCreateError: {}", err);
+    }
     let mut locked_guild_cache = cache_manager.lock().await;
-    match locked_guild_cache.add_or_update_guild(&ctx, guild_id).await {
+    match locked_guild_cache.add_or_update_guild(&ctx, guild_id) {
         Ok(_) => (),
         Err(err) => {
             return eprintln!("GuildCreateError: {}", err);
@@ -19,7 +19,13 @@ use super::{
 #[async_trait]
 impl EventHandler for Handler {
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        handle_interaction_create(&ctx, interaction).await
+        handle_interaction_create(
+            &ctx,
+            interaction,
+            self.cache_manager.clone(),
+            self.connection_state.clone(),
+        )
+        .await
     }
 
     async fn guild_role_delete(
@@ -113,6 +119,12 @@ impl EventHandler for Handler {
     }
 
     async fn ready(&self, ctx: Context, ready: Ready) {
-        handle_ready(ctx, ready, self.cache_manager.clone()).await;
+        handle_ready(
+            ctx,
+            ready,
+            self.cache_manager.clone(),
+            self.connection_state.clone(),
+        )
+        .await;
     }
 }
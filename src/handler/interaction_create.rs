@@ -1,13 +1,27 @@
 use serenity::{client::Context, model::prelude::Interaction};
 
+use crate::{cache::CacheManager, ws::connection_state::ConnectionState};
+
 use super::{
     application_command_interaction::handle_application_command_interaction,
-    message_component_interaction::handle_message_component_interaction,
+    message_component_interaction::handle_message_component_interaction, ArcMutex,
 };
 
-pub async fn handle_interaction_create(ctx: &Context, interaction: Interaction) {
+pub async fn handle_interaction_create(
+    ctx: &Context,
+    interaction: Interaction,
+    cache_manager: ArcMutex<CacheManager>,
+    connection_state: ArcMutex<ConnectionState>,
+) {
     if let Some(command) = interaction.as_application_command() {
-        match handle_application_command_interaction(ctx, command).await {
+        match handle_application_command_interaction(
+            ctx,
+            command,
+            cache_manager.clone(),
+            connection_state,
+        )
+        .await
+        {
             Ok(_) => (),
             Err(err) => {
                 eprintln!("{}", err);
@@ -24,7 +38,7 @@ pub async fn handle_interaction_create(ctx: &Context, interaction: Interaction)
                 );
             }
         };
-        match handle_message_component_interaction(ctx, message_component).await {
+        match handle_message_component_interaction(ctx, message_component, cache_manager).await {
             Ok(_) => (),
             Err(err) => {
                 return eprintln!("InteractionCreateError: {}", err);
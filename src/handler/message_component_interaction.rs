@@ -1,19 +1,38 @@
 use serenity::{client::Context, model::prelude::message_component::MessageComponentInteraction};
 
 use crate::{
-    cache::split::Split,
+    cache::{split::Split, CacheManager},
     components::message::{
         remove_pmb_roles::handle_remove_pmb_roles, select_role::handle_select_role,
+        unsubscribe::handle_unsubscribe,
     },
     Result,
 };
 
+use super::ArcMutex;
+
 pub async fn handle_message_component_interaction(
     ctx: &Context,
     message_component: &MessageComponentInteraction,
+    cache_manager: ArcMutex<CacheManager>,
 ) -> Result<()> {
+    // Unsubscribe buttons carry the id of the subscription they belong to.
+    if let Some(subscription_id) = message_component
+        .data
+        .custom_id
+        .strip_prefix("unsubscribe:")
+        .and_then(|id| id.parse::<i64>().ok())
+    {
+        return match handle_unsubscribe(ctx, message_component, subscription_id, cache_manager)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => Err(format!("Error while handling interaction: {}", err).into()),
+        };
+    }
     let custom_id = match message_component.data.custom_id.as_str() {
         "remove_pmb_roles" => handle_remove_pmb_roles(&ctx, &message_component).await,
+        "select_nether_role" => handle_select_role(ctx, message_component, Split::Nether).await,
         "select_structure1_role" => {
             handle_select_role(&ctx, &message_component, Split::FirstStructure).await
         }
@@ -25,6 +44,7 @@ pub async fn handle_message_component_interaction(
         "select_end_enter_role" => {
             handle_select_role(&ctx, &message_component, Split::EndEnter).await
         }
+        "select_finish_role" => handle_select_role(ctx, message_component, Split::Finish).await,
         _ => Err(format!("Unknown custom id: {}.", message_component.data.custom_id).into()),
     };
     match custom_id {
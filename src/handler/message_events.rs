@@ -22,10 +22,12 @@ pub async fn handle_message_events(
             );
         }
     };
-    if name != "pacemanbot-runner-names" {
-        return println!(
-            "Skipping message delete because it was not sent in #pacemanbot-runner-names.",
-        );
+    let is_config_channel = match guild_cache.lock().await.cache.get(&guild_id) {
+        Some(guild_data) => guild_data.config_channel == Some(channel_id),
+        None => false,
+    };
+    if name != "pacemanbot-runner-names" && !is_config_channel {
+        return println!("Skipping message delete because it was not sent in the config channel.",);
     }
     match handle_update_cache(ctx, guild_id, guild_cache).await {
         Ok(_) => (),
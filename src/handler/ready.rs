@@ -4,48 +4,108 @@ use serenity::{client::Context, model::prelude::Ready};
 use tokio::time::sleep;
 
 use crate::{
-    cache::CacheManager,
-    dispatcher::Dispatcher,
-    ws::{consts::WS_TIMEOUT_FOR_RETRY, WSManager},
+    cache::{consts::LEADERBOARD_ROLLOVER_INTERVAL_SECS, CacheManager},
+    dispatcher::dispatch_queue::DispatchQueue,
+    utils::rollover_leaderboards::rollover_leaderboards,
+    ws::{
+        backoff::Backoff, connection_state::ConnectionState, consts::REPLAY_CACHE_WAIT_SECS,
+        ReplayManager, WSManager,
+    },
 };
 
 use super::ArcMutex;
 
-pub async fn ws_event_loop(ctx: Arc<Context>, cache_manager: ArcMutex<CacheManager>) {
+async fn wait_for_retry(backoff: &mut Backoff, connection_state: &ArcMutex<ConnectionState>) {
+    let retry_in = backoff.next_delay();
+    *connection_state.lock().await = ConnectionState::BackingOff {
+        attempt: backoff.attempt,
+        retry_in,
+    };
+    if !retry_in.is_zero() {
+        println!("Trying again in {} seconds...", retry_in.as_secs_f32());
+        sleep(retry_in).await;
+    }
+}
+
+pub async fn ws_event_loop(
+    ctx: Arc<Context>,
+    cache_manager: ArcMutex<CacheManager>,
+    connection_state: ArcMutex<ConnectionState>,
+) {
+    let mut backoff = Backoff::new();
+    let mut dispatch_queue = DispatchQueue::new(ctx, cache_manager);
     loop {
+        *connection_state.lock().await = ConnectionState::Connecting {
+            attempt: backoff.attempt,
+        };
         let mut manager = match WSManager::new().await {
             Ok(manager) => manager,
             Err(err) => {
                 eprintln!("WSManager init error: {}", err);
-                println!("Trying again in {} seconds...", WS_TIMEOUT_FOR_RETRY);
-                sleep(Duration::from_secs(WS_TIMEOUT_FOR_RETRY)).await;
+                wait_for_retry(&mut backoff, &connection_state).await;
                 continue;
             }
         };
-        loop {
-            let response = match manager.get_next().await {
-                Some(response) => response,
-                None => break,
-            };
-            let dispatcher = Dispatcher {
-                ctx: ctx.clone(),
-                response,
-                cache_manager: cache_manager.clone(),
-            };
-            match dispatcher.dispatch().await {
-                Ok(_) => (),
-                Err(err) => {
-                    eprintln!("Dispatch error: {}", err);
-                    continue;
-                }
-            };
+        *connection_state.lock().await = ConnectionState::Connected;
+        while let Some(response) = manager.get_next().await {
+            backoff.reset();
+            dispatch_queue.push(response).await;
+        }
+        wait_for_retry(&mut backoff, &connection_state).await;
+    }
+}
+
+pub async fn replay_event_loop(
+    ctx: Arc<Context>,
+    cache_manager: ArcMutex<CacheManager>,
+    guild_count: usize,
+) {
+    // Guilds are cached as their create events arrive, so give them a chance to
+    // land before the first recorded response is dispatched.
+    for _ in 0..REPLAY_CACHE_WAIT_SECS {
+        if cache_manager.lock().await.cache.len() >= guild_count {
+            break;
         }
+        sleep(Duration::from_secs(1)).await;
+    }
+    let mut manager = match ReplayManager::new().await {
+        Ok(manager) => manager,
+        Err(err) => return eprintln!("ReplayManager init error: {}", err),
+    };
+    println!("Replaying recorded responses at speed: {}.", manager.speed);
+    let mut dispatch_queue = DispatchQueue::new(ctx, cache_manager);
+    while let Some(response) = manager.get_next().await {
+        dispatch_queue.push(response).await;
     }
+    dispatch_queue.flush().await;
+    println!("Replay finished.");
 }
 
-pub async fn handle_ready(ctx: Context, ready: Ready, cache_manager: ArcMutex<CacheManager>) {
+pub async fn leaderboard_rollover_loop(ctx: Arc<Context>, cache_manager: ArcMutex<CacheManager>) {
+    loop {
+        sleep(Duration::from_secs(LEADERBOARD_ROLLOVER_INTERVAL_SECS)).await;
+        rollover_leaderboards(&ctx, &cache_manager).await;
+    }
+}
+
+pub async fn handle_ready(
+    ctx: Context,
+    ready: Ready,
+    cache_manager: ArcMutex<CacheManager>,
+    connection_state: ArcMutex<ConnectionState>,
+) {
     println!("{} is connected!", ready.user.name);
     let cache_manager = cache_manager.clone();
     let ctx = Arc::new(ctx);
-    tokio::spawn(async move { ws_event_loop(ctx, cache_manager).await });
+    let rollover_ctx = ctx.clone();
+    let rollover_cache_manager = cache_manager.clone();
+    tokio::spawn(
+        async move { leaderboard_rollover_loop(rollover_ctx, rollover_cache_manager).await },
+    );
+    if ReplayManager::is_enabled() {
+        let guild_count = ready.guilds.len();
+        tokio::spawn(async move { replay_event_loop(ctx, cache_manager, guild_count).await });
+    } else {
+        tokio::spawn(async move { ws_event_loop(ctx, cache_manager, connection_state).await });
+    }
 }
@@ -10,7 +10,7 @@ pub async fn handle_update_cache(
     cache_manager: ArcMutex<CacheManager>,
 ) -> Result<()> {
     let mut locked_guild_cache = cache_manager.lock().await;
-    match locked_guild_cache.add_or_update_guild(ctx, guild_id).await {
+    match locked_guild_cache.add_or_update_guild(ctx, guild_id) {
         Ok(_) => (),
         Err(err) => return Err(format!("UpdateCacheError: {}", err).into()),
     };
@@ -1,13 +1,16 @@
 use dotenv::dotenv;
 mod cache;
 mod components;
+mod db;
 mod dispatcher;
 mod handler;
+mod rules;
 #[cfg(test)]
 mod tests;
 mod utils;
 mod ws;
 use cache::CacheManager;
+use db::Database;
 use handler::Handler;
 use serenity::client::Client;
 use serenity::framework::standard::StandardFramework;
@@ -16,6 +19,7 @@ use serenity::prelude::GatewayIntents;
 use std::env;
 use std::error::Error;
 use std::sync::Arc;
+use ws::connection_state::ConnectionState;
 
 pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
@@ -26,7 +30,9 @@ async fn main() -> Result<()> {
 
     let framework = StandardFramework::new();
 
-    let cache_manager = Arc::new(Mutex::new(CacheManager::new()));
+    let db = Database::open()?;
+    let cache_manager = Arc::new(Mutex::new(CacheManager::new(db)));
+    let connection_state = Arc::new(Mutex::new(ConnectionState::Connecting { attempt: 0 }));
 
     let mut intents = GatewayIntents::all();
     intents.remove(GatewayIntents::GUILD_MEMBERS);
@@ -34,7 +40,10 @@ async fn main() -> Result<()> {
     intents.remove(GatewayIntents::MESSAGE_CONTENT);
 
     let mut client = Client::builder(&token, intents)
-        .event_handler(Handler { cache_manager })
+        .event_handler(Handler {
+            cache_manager,
+            connection_state,
+        })
         .framework(framework)
         .await?;
     client.start().await?;
@@ -0,0 +1,109 @@
+use crate::{
+    cache::split::Split,
+    dispatcher::{get_run_info::get_run_info, run_info::RunType},
+    ws::response::{Event, Response},
+};
+
+use super::{CompareOp, Expr, Field, Flag, Rule, Value};
+
+struct RuleContext<'a> {
+    response: &'a Response,
+    splits: Vec<(Split, u64)>,
+    bastionless: bool,
+}
+
+impl<'a> RuleContext<'a> {
+    fn new(response: &'a Response, last_event: &Event) -> RuleContext<'a> {
+        let event_list = match response.event_list.iter().position(|evt| evt == last_event) {
+            Some(idx) => &response.event_list[..=idx],
+            None => &response.event_list[..],
+        };
+        let mut splits = vec![];
+        let mut bastionless = false;
+        for event in event_list {
+            let run_info = match get_run_info(response, event) {
+                Some(info) => info,
+                None => continue,
+            };
+            if let RunType::Bastionless = run_info.run_type {
+                bastionless = true;
+            }
+            // Only the first time a split is reached counts.
+            if !splits.iter().any(|(split, _)| *split == run_info.split) {
+                splits.push((run_info.split, event.igt as u64));
+            }
+        }
+        RuleContext {
+            response,
+            splits,
+            bastionless,
+        }
+    }
+
+    fn compare<T: PartialOrd>(lhs: T, op: &CompareOp, rhs: T) -> bool {
+        match op {
+            CompareOp::Lt => lhs < rhs,
+            CompareOp::Le => lhs <= rhs,
+            CompareOp::Gt => lhs > rhs,
+            CompareOp::Ge => lhs >= rhs,
+            CompareOp::Eq => lhs == rhs,
+            CompareOp::Ne => lhs != rhs,
+        }
+    }
+
+    fn eval(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::And(lhs, rhs) => self.eval(lhs) && self.eval(rhs),
+            Expr::Or(lhs, rhs) => self.eval(lhs) || self.eval(rhs),
+            Expr::Not(expr) => !self.eval(expr),
+            Expr::Flag(Flag::Bastionless) => self.bastionless,
+            Expr::Flag(Flag::Live) => self.response.user.live_account.is_some(),
+            Expr::Compare(field, op, value) => match (field, value) {
+                // Splits that haven't been reached yet never match.
+                (Field::Split(split), Value::Millis(millis)) => {
+                    match self.splits.iter().find(|(s, _)| s == split) {
+                        Some((_, igt)) => Self::compare(*igt, op, *millis),
+                        None => false,
+                    }
+                }
+                (Field::Item(item), Value::Count(count)) => Self::compare(
+                    self.response
+                        .item_data
+                        .as_ref()
+                        .and_then(|data| data.estimated_counts.get(item).copied())
+                        .unwrap_or(0),
+                    op,
+                    *count,
+                ),
+                (Field::Runner, Value::Name(name)) => Self::compare(
+                    self.response.nickname.to_lowercase(),
+                    op,
+                    name.to_lowercase(),
+                ),
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Expr {
+    fn uses_items(&self) -> bool {
+        match self {
+            Expr::And(lhs, rhs) | Expr::Or(lhs, rhs) => lhs.uses_items() || rhs.uses_items(),
+            Expr::Not(expr) => expr.uses_items(),
+            Expr::Compare(Field::Item(_), _, _) => true,
+            Expr::Compare(_, _, _) | Expr::Flag(_) => false,
+        }
+    }
+}
+
+impl Rule {
+    // Item counts that are missing from the item data count as 0, but a rule about
+    // items never matches a run that has no item data at all, not even under `not`.
+    pub fn matches(&self, response: &Response, last_event: &Event) -> bool {
+        if response.item_data.is_none() && self.condition.uses_items() {
+            return false;
+        }
+        RuleContext::new(response, last_event).eval(&self.condition)
+    }
+}
@@ -0,0 +1,133 @@
+use serenity::model::id::RoleId;
+
+use crate::{utils::mins_secs_to_millis::mins_secs_to_millis, Result};
+
+use super::CompareOp;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Token {
+    Ident(String),
+    Str(String),
+    Number(u32),
+    Time(u64),
+    Op(CompareOp),
+    Role(RoleId),
+    LParen,
+    RParen,
+    Arrow,
+}
+
+fn take_while(chars: &[char], start: usize, pred: impl Fn(char) -> bool) -> usize {
+    let mut end = start;
+    while end < chars.len() && pred(chars[end]) {
+        end += 1;
+    }
+    end
+}
+
+pub fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let chars = source.chars().collect::<Vec<_>>();
+    let mut tokens = vec![];
+    let mut idx = 0;
+    while idx < chars.len() {
+        let c = chars[idx];
+        if c.is_whitespace() {
+            idx += 1;
+            continue;
+        }
+        let rest = chars[idx..].iter().collect::<String>();
+        if rest.starts_with("->") {
+            tokens.push(Token::Arrow);
+            idx += 2;
+        } else if rest.starts_with("<@&") {
+            let end = take_while(&chars, idx + 3, |c| c.is_ascii_digit());
+            if end >= chars.len() || chars[end] != '>' {
+                return Err(
+                    format!("RuleError: unclosed role mention at position: {}.", idx).into(),
+                );
+            }
+            let role_id = chars[idx + 3..end]
+                .iter()
+                .collect::<String>()
+                .parse::<u64>()?;
+            tokens.push(Token::Role(RoleId(role_id)));
+            idx = end + 1;
+        } else if let Some((op, len)) = [
+            ("<=", CompareOp::Le),
+            (">=", CompareOp::Ge),
+            ("==", CompareOp::Eq),
+            ("!=", CompareOp::Ne),
+            ("<", CompareOp::Lt),
+            (">", CompareOp::Gt),
+            ("=", CompareOp::Eq),
+        ]
+        .into_iter()
+        .find(|(op, _)| rest.starts_with(op))
+        .map(|(op, compare_op)| (compare_op, op.len()))
+        {
+            tokens.push(Token::Op(op));
+            idx += len;
+        } else if c == '(' {
+            tokens.push(Token::LParen);
+            idx += 1;
+        } else if c == ')' {
+            tokens.push(Token::RParen);
+            idx += 1;
+        } else if c == '"' {
+            let end = take_while(&chars, idx + 1, |c| c != '"');
+            if end >= chars.len() {
+                return Err(format!("RuleError: unclosed quote at position: {}.", idx).into());
+            }
+            tokens.push(Token::Str(chars[idx + 1..end].iter().collect()));
+            idx = end + 1;
+        } else if c.is_ascii_digit() {
+            let end = take_while(&chars, idx, |c| c.is_ascii_digit());
+            let number = chars[idx..end].iter().collect::<String>().parse::<u32>()?;
+            if end < chars.len() && chars[end] == ':' {
+                let seconds_end = take_while(&chars, end + 1, |c| c.is_ascii_digit());
+                if seconds_end - (end + 1) != 2 {
+                    return Err(format!(
+                        "RuleError: expected time in the format 'm:ss' at position: {}.",
+                        idx
+                    )
+                    .into());
+                }
+                let seconds = chars[end + 1..seconds_end]
+                    .iter()
+                    .collect::<String>()
+                    .parse::<u64>()?;
+                if seconds >= 60 {
+                    return Err(format!(
+                        "RuleError: invalid seconds in time at position: {}.",
+                        idx
+                    )
+                    .into());
+                }
+                let millis = match mins_secs_to_millis((number as u64, seconds)) {
+                    Some(millis) => millis,
+                    None => {
+                        return Err(
+                            format!("RuleError: time out of range at position: {}.", idx).into(),
+                        )
+                    }
+                };
+                tokens.push(Token::Time(millis));
+                idx = seconds_end;
+            } else {
+                tokens.push(Token::Number(number));
+                idx = end;
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            let end = take_while(&chars, idx, |c| c.is_alphanumeric() || c == '_');
+            tokens.push(Token::Ident(chars[idx..end].iter().collect()));
+            idx = end;
+        } else {
+            return Err(format!(
+                "RuleError: unexpected character: '{}' at position: {}.",
+                c, idx
+            )
+            .into());
+        }
+    }
+    Ok(tokens)
+}
@@ -0,0 +1,59 @@
+use serenity::model::id::RoleId;
+
+use crate::{cache::split::Split, ws::response::Item};
+
+pub mod eval;
+pub mod lexer;
+pub mod parser;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum CompareOp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Field {
+    Split(Split),
+    Item(Item),
+    Runner,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Flag {
+    Bastionless,
+    Live,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+    Millis(u64),
+    Count(u32),
+    Name(String),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare(Field, CompareOp, Value),
+    Flag(Flag),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Rule {
+    pub condition: Expr,
+    pub role_id: RoleId,
+}
+
+#[derive(Debug, Clone)]
+pub struct GuildRule {
+    pub id: i64,
+    pub source: String,
+    pub rule: Rule,
+}
@@ -0,0 +1,132 @@
+use serenity::model::id::RoleId;
+
+use crate::{cache::split::Split, ws::response::Item, Result};
+
+use super::{
+    lexer::{tokenize, Token},
+    CompareOp, Expr, Field, Flag, Rule, Value,
+};
+
+struct Parser {
+    tokens: Vec<Token>,
+    idx: usize,
+}
+
+fn keyword(token: Option<&Token>, keyword: &str) -> bool {
+    matches!(token, Some(Token::Ident(ident)) if ident.eq_ignore_ascii_case(keyword))
+}
+
+fn field_from_ident(ident: &str) -> Option<Field> {
+    if let Some(split) = Split::from_command_param(ident) {
+        return Some(Field::Split(split));
+    }
+    Some(match ident {
+        "runner" => Field::Runner,
+        "pearls" => Field::Item(Item::MinecraftEnderPearl),
+        "rods" => Field::Item(Item::MinecraftBlazeRod),
+        "obsidian" => Field::Item(Item::MinecraftObsidian),
+        _ => return None,
+    })
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.idx)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.idx).cloned();
+        self.idx += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Expr> {
+        let mut expr = self.parse_and()?;
+        while keyword(self.peek(), "or") {
+            self.idx += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr> {
+        let mut expr = self.parse_not()?;
+        while keyword(self.peek(), "and") {
+            self.idx += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_not()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr> {
+        if keyword(self.peek(), "not") {
+            self.idx += 1;
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr> {
+        let ident = match self.next() {
+            Some(Token::LParen) => {
+                let expr = self.parse_or()?;
+                return match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err("RuleError: expected ')'.".into()),
+                };
+            }
+            Some(Token::Ident(ident)) => ident.to_lowercase(),
+            Some(token) => return Err(format!("RuleError: unexpected token: {:?}.", token).into()),
+            None => return Err("RuleError: unexpected end of rule.".into()),
+        };
+        match ident.as_str() {
+            "bastionless" => return Ok(Expr::Flag(Flag::Bastionless)),
+            "live" => return Ok(Expr::Flag(Flag::Live)),
+            _ => (),
+        };
+        let field = match field_from_ident(&ident) {
+            Some(field) => field,
+            None => return Err(format!("RuleError: unknown name: '{}'.", ident).into()),
+        };
+        let op = match self.next() {
+            Some(Token::Op(op)) => op,
+            _ => return Err(format!("RuleError: expected a comparison after '{}'.", ident).into()),
+        };
+        let value = match (&field, self.next()) {
+            (Field::Split(_), Some(Token::Time(millis))) => Value::Millis(millis),
+            // Whole numbers are minutes, the same as everywhere else in the bot.
+            (Field::Split(_), Some(Token::Number(minutes))) => {
+                Value::Millis(minutes as u64 * 60000)
+            }
+            (Field::Item(_), Some(Token::Number(count))) => Value::Count(count),
+            (Field::Runner, Some(Token::Ident(name) | Token::Str(name))) => {
+                if op != CompareOp::Eq && op != CompareOp::Ne {
+                    return Err("RuleError: runner can only be compared with '==' or '!='.".into());
+                }
+                Value::Name(name)
+            }
+            (_, token) => {
+                return Err(
+                    format!("RuleError: invalid value: {:?} for '{}'.", token, ident).into(),
+                )
+            }
+        };
+        Ok(Expr::Compare(field, op, value))
+    }
+}
+
+pub fn parse_rule(source: &str) -> Result<Rule> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        idx: 0,
+    };
+    let condition = parser.parse_or()?;
+    let role_id: RoleId = match (parser.next(), parser.next()) {
+        (Some(Token::Arrow), Some(Token::Role(role_id))) => role_id,
+        _ => return Err("RuleError: expected '-> @role' at the end of the rule.".into()),
+    };
+    if parser.peek().is_some() {
+        return Err("RuleError: unexpected input after the role.".into());
+    }
+    Ok(Rule { condition, role_id })
+}
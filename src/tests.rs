@@ -1,12 +1,107 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use serenity::{
+    builder::CreateEmbedAuthor,
+    model::id::{ChannelId, GuildId, MessageId, RoleId, UserId},
+};
+
 use crate::{
-    cache::{players::PlayerSplitsData, split::Split},
+    cache::{
+        channel_kind::ChannelKind,
+        consts::{DM_RATE_LIMIT_COUNT, LEADERBOARD_PAGE_LIMIT},
+        embed_template::EmbedTemplate,
+        guild_data::GuildData,
+        item_requirement::{ItemRequirement, RequirementTarget},
+        leaderboard::{
+            LeaderboardEntry, LeaderboardPeriod, LEADERBOARD_ARCHIVE_HEADER, LEADERBOARD_HEADER,
+        },
+        players::PlayerSplitsData,
+        role_data::RoleData,
+        route::Route,
+        runs::{RunData, RunMessage, SentMessage},
+        split::Split,
+        subscription::Subscription,
+        CacheManager,
+    },
+    db::{consts::LEADERBOARD_ROLLOVER_SETTING, Database},
+    dispatcher::{
+        get_roles_to_ping::get_roles_to_ping,
+        get_run_info::{get_run_info, get_run_type},
+        guild_message::{EvolvingMessage, GuildMessage},
+        reset_event::handle_reset_event,
+        run_info::RunType,
+        runner_rename::handle_runner_rename,
+    },
+    rules::parser::parse_rule,
     utils::{
         extract_name_and_splits_from_line::extract_name_and_splits_from_line,
         extract_split_from_pb_role_name::extract_split_from_pb_role_name,
-        extract_split_from_role_name::extract_split_from_role_name,
+        extract_split_from_role_name::extract_split_from_role_name, format_uuid::format_uuid,
+        import_leaderboard_message::parse_leaderboard_message, parse_date::parse_date,
+        parse_time::parse_time, rollover_leaderboards::get_rollover, send_audit_log::is_audited,
+    },
+    ws::{
+        backoff::Backoff,
+        consts::WS_BACKOFF_MAX_SECS,
+        response::{Event, EventId, Item, ItemData, Response, User},
     },
 };
 
+// Builds an event where the real time matches the in-game time.
+fn event(event_id: EventId, igt: i64) -> Event {
+    Event {
+        event_id,
+        rta: igt,
+        igt,
+    }
+}
+
+// Builds a 1.16.1 response for a runner without a live account or item data.
+fn response(event_list: Vec<Event>) -> Response {
+    Response {
+        game_version: Some("1.16.1".to_string()),
+        world_id: "world".to_string(),
+        event_list,
+        context_event_list: vec![],
+        user: User {
+            uuid: "uuid".to_string(),
+            live_account: None,
+        },
+        _is_cheated: false,
+        _is_hidden: false,
+        last_updated: 0,
+        item_data: None,
+        nickname: "runner".to_string(),
+    }
+}
+
+// Builds a public guild that sends everything to a single channel with default settings.
+fn guild_data() -> GuildData {
+    GuildData {
+        name: "guild".to_string(),
+        pace_channel: ChannelId(1),
+        finish_channel: ChannelId(1),
+        lb_channel: None,
+        config_channel: None,
+        audit_channel: None,
+        players: HashMap::new(),
+        runner_uuids: HashMap::new(),
+        is_private: false,
+        roles: vec![],
+        evolving_messages: false,
+        reset_ping_window: None,
+        item_requirements: vec![],
+        rules: vec![],
+        routes: vec![],
+        subscriptions: vec![],
+        embed_template: EmbedTemplate::default(),
+        leaderboard_period: None,
+    }
+}
+
 #[test]
 pub fn test_extract_split_from_role_name() -> Result<(), Box<dyn std::error::Error>> {
     assert_eq!(
@@ -25,6 +120,14 @@ pub fn test_extract_split_from_role_name() -> Result<(), Box<dyn std::error::Err
         extract_split_from_role_name("*EE10:4")?,
         (Split::EndEnter, 10, 40)
     );
+    assert_eq!(
+        extract_split_from_role_name("*F9:3")?,
+        (Split::Finish, 9, 30)
+    );
+    assert_eq!(
+        extract_split_from_role_name("*N1:3")?,
+        (Split::Nether, 1, 30)
+    );
     Ok(())
 }
 
@@ -49,11 +152,11 @@ pub fn test_extract_split_from_pb_role_name() {
 #[test]
 pub fn test_extract_name_and_splits_from_line() -> Result<(), Box<dyn std::error::Error>> {
     let mut split_data = PlayerSplitsData {
-        first_structure: 10,
-        second_structure: 20,
-        blind: 30,
-        eye_spy: 40,
-        end_enter: 50,
+        first_structure: 600000,
+        second_structure: 1200000,
+        blind: 1800000,
+        eye_spy: 2400000,
+        end_enter: 3000000,
         finish: None,
     };
     assert_eq!(
@@ -65,7 +168,7 @@ pub fn test_extract_name_and_splits_from_line() -> Result<(), Box<dyn std::error
         ("name_name_".to_string(), split_data)
     );
 
-    split_data.finish = Some(60);
+    split_data.finish = Some(3600000);
     assert_eq!(
         extract_name_and_splits_from_line("SathyaPramodh: 10/20/30/40/50/60")?,
         ("SathyaPramodh".to_string(), split_data)
@@ -74,5 +177,784 @@ pub fn test_extract_name_and_splits_from_line() -> Result<(), Box<dyn std::error
         extract_name_and_splits_from_line("name_name_: 10/20/30/40/50/60")?,
         ("name_name_".to_string(), split_data)
     );
+
+    split_data.first_structure = 210000;
+    assert_eq!(
+        extract_name_and_splits_from_line("SathyaPramodh: 3:30/20/30/40:00/50/60")?,
+        ("SathyaPramodh".to_string(), split_data)
+    );
+    assert!(extract_name_and_splits_from_line("SathyaPramodh: 3:75/20/30/40/50").is_err());
+    assert_eq!(parse_time("3:05")?, 185000);
+    assert!(parse_time("3:+5").is_err());
+    assert!(parse_time("+3").is_err());
+    assert!(parse_time(&u64::MAX.to_string()).is_err());
+    Ok(())
+}
+
+#[test]
+pub fn test_cache_manager_dispatched_events() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cache_manager = CacheManager::new(Database::open_in_memory()?);
+    let world_id = String::from("world");
+    assert!(!cache_manager.is_dispatched(&world_id, &EventId::RsgEnterBastion, 0));
+    cache_manager.mark_dispatched(&world_id, &EventId::RsgEnterBastion, 0);
+    assert!(cache_manager.is_dispatched(&world_id, &EventId::RsgEnterBastion, 0));
+    assert!(!cache_manager.is_dispatched(&world_id, &EventId::RsgEnterFortress, 0));
+    assert!(!cache_manager.is_dispatched(&String::from("other"), &EventId::RsgEnterBastion, 0));
+    // A runner that rejoins and leaves again ends the run a second time.
+    cache_manager.mark_dispatched(&world_id, &EventId::CommonLeaveWorld, 0);
+    assert!(!cache_manager.is_dispatched(&world_id, &EventId::CommonLeaveWorld, 1));
+    Ok(())
+}
+
+#[test]
+pub fn test_get_run_info_for_batched_events() {
+    let response = Response {
+        context_event_list: vec![
+            event(EventId::RsgObtainCryingObsidian, 120000),
+            event(EventId::RsgLootBastion, 150000),
+            event(EventId::RsgObtainBlazeRod, 200000),
+        ],
+        ..response(vec![
+            event(EventId::RsgEnterNether, 60000),
+            event(EventId::RsgEnterBastion, 90000),
+            event(EventId::RsgEnterFortress, 180000),
+        ])
+    };
+    let nether_info = get_run_info(&response, &response.event_list[0]).unwrap();
+    assert_eq!(nether_info.split, Split::Nether);
+    let bastion_info = get_run_info(&response, &response.event_list[1]).unwrap();
+    assert_eq!(bastion_info.split, Split::FirstStructure);
+    let fortress_info = get_run_info(&response, &response.event_list[2]).unwrap();
+    assert_eq!(fortress_info.split, Split::SecondStructure);
+}
+
+#[test]
+pub fn test_backoff_delays() {
+    let mut backoff = Backoff::new();
+    assert!(backoff.next_delay().is_zero());
+    let mut previous_max = Duration::ZERO;
+    for _ in 0..20 {
+        let max_delay = backoff.max_delay();
+        let delay = backoff.next_delay();
+        assert!(max_delay >= previous_max);
+        assert!(delay >= max_delay / 2 && delay <= max_delay);
+        assert!(delay <= Duration::from_secs(WS_BACKOFF_MAX_SECS));
+        previous_max = max_delay;
+    }
+    assert_eq!(previous_max, Duration::from_secs(WS_BACKOFF_MAX_SECS));
+    backoff.reset();
+    assert!(backoff.next_delay().is_zero());
+}
+
+#[test]
+pub fn test_response_with_unknown_event_id_and_item() -> Result<(), Box<dyn std::error::Error>> {
+    let response: Response = serde_json::from_str(
+        r#"{
+            "gameVersion": "1.16.1",
+            "worldId": "world",
+            "eventList": [
+                {"eventId": "rsg.enter_bastion", "rta": 100000, "igt": 90000},
+                {"eventId": "rsg.new_event", "rta": 110000, "igt": 100000}
+            ],
+            "contextEventList": [],
+            "user": {"uuid": "uuid", "liveAccount": null},
+            "isCheated": false,
+            "isHidden": false,
+            "lastUpdated": 0,
+            "itemData": {
+                "estimatedCounts": {"minecraft:ender_pearl": 12, "minecraft:new_item": 1},
+                "usages": null
+            },
+            "nickname": "runner"
+        }"#,
+    )?;
+    assert_eq!(response.event_list[0].event_id, EventId::RsgEnterBastion);
+    assert_eq!(
+        response.event_list[1].event_id,
+        EventId::Unknown("rsg.new_event".to_string())
+    );
+    let counts = response.item_data.unwrap().estimated_counts;
+    assert_eq!(counts.get(&Item::MinecraftEnderPearl), Some(&12));
+    assert_eq!(
+        counts.get(&Item::Unknown("minecraft:new_item".to_string())),
+        Some(&1)
+    );
+    Ok(())
+}
+
+#[test]
+pub fn test_database_runners_and_settings() -> Result<(), Box<dyn std::error::Error>> {
+    let db = Database::open_in_memory()?;
+    let guild_id = GuildId(1);
+    let mut split_data = PlayerSplitsData {
+        first_structure: 600000,
+        second_structure: 1200000,
+        blind: 1800000,
+        eye_spy: 2400000,
+        end_enter: 3000000,
+        finish: None,
+    };
+    db.upsert_player(guild_id, "SathyaPramodh", None, &split_data)?;
+    split_data.finish = Some(3600000);
+    db.upsert_player(guild_id, "sathyapramodh", None, &split_data)?;
+    let players = db.get_players(guild_id)?;
+    assert_eq!(players.len(), 1);
+    assert_eq!(players.get("sathyapramodh"), Some(&split_data));
+    assert!(db.get_players(GuildId(2))?.is_empty());
+    assert!(db.remove_player(guild_id, "SATHYAPRAMODH")?);
+    assert!(!db.remove_player(guild_id, "SathyaPramodh")?);
+
+    assert_eq!(db.get_setting(guild_id, "key")?, None);
+    db.set_setting(guild_id, "key", "value")?;
+    db.set_setting(guild_id, "key", "new_value")?;
+    assert_eq!(
+        db.get_setting(guild_id, "key")?,
+        Some("new_value".to_string())
+    );
+    Ok(())
+}
+
+#[test]
+pub fn test_run_message_keeps_split_history() {
+    let mut run_message = RunMessage::new(ChannelId(1));
+    let link = ("Splits".to_string(), "[Link]()".to_string(), false);
+    run_message.add_split("1:20 - Enter Nether".to_string(), vec![link.clone()]);
+    let fields = run_message.add_split("4:10 - Enter Fortress".to_string(), vec![link.clone()]);
+    assert_eq!(
+        fields,
+        vec![
+            ("1:20 - Enter Nether".to_string(), String::new(), false),
+            ("4:10 - Enter Fortress".to_string(), String::new(), false),
+            link,
+        ]
+    );
+    assert_eq!(run_message.fields, fields);
+    assert_eq!(run_message.message_id, None);
+
+    let guild_message = |split_desc: &str, ping_content: Option<&str>| GuildMessage {
+        guild_name: "guild".to_string(),
+        channel_id: ChannelId(1),
+        runner_name: "runner".to_string(),
+        split_desc: split_desc.to_string(),
+        author: CreateEmbedAuthor::default(),
+        fields: vec![(split_desc.to_string(), String::new(), false)],
+        colour: None,
+        ping_content: ping_content.map(str::to_string),
+        removable_roles: vec![],
+        evolving: Some(EvolvingMessage {
+            channel_id: ChannelId(1),
+            message_id: None,
+        }),
+    };
+    let mut message = guild_message("Enter Nether", Some("-# <@&1> <@&2>"));
+    let later_message = guild_message("Enter Fortress", Some("-# <@&2> <@&3>"));
+    assert!(message.continues(&later_message));
+    message.merge(later_message);
+    assert_eq!(message.split_desc, "Enter Fortress");
+    assert_eq!(
+        message.ping_content.as_deref(),
+        Some("-# <@&1> <@&2> <@&3>")
+    );
+    let mut later_message = guild_message("Blind", None);
+    later_message.evolving = None;
+    assert!(!message.continues(&later_message));
+}
+
+#[test]
+pub fn test_handle_reset_event() -> Result<(), Box<dyn std::error::Error>> {
+    let mut response = response(vec![
+        event(EventId::RsgEnterNether, 60000),
+        event(EventId::RsgEnterBastion, 90000),
+        event(EventId::RsgEnterFortress, 180000),
+        event(EventId::CommonLeaveWorld, 200000),
+    ]);
+    let guild_id = GuildId(1);
+    let guild_data = GuildData {
+        evolving_messages: true,
+        reset_ping_window: Some(5),
+        ..guild_data()
+    };
+    let sent_message = |message_id: u64, is_ping: bool| SentMessage {
+        channel_id: ChannelId(1),
+        message_id: MessageId(message_id),
+        author: CreateEmbedAuthor::default(),
+        fields: vec![],
+        colour: None,
+        is_ping,
+        sent_at: Instant::now(),
+    };
+    let mut run_data = RunData::new();
+    run_data.sent.insert(
+        guild_id,
+        vec![sent_message(1, false), sent_message(2, true)],
+    );
+    run_data
+        .messages
+        .insert(guild_id, RunMessage::new(ChannelId(1)));
+
+    let last_event = response.event_list[3].clone();
+    let run_end =
+        handle_reset_event(&response, &last_event, guild_id, &guild_data, &run_data).unwrap();
+    assert_eq!(run_end.ended_field.1, "Reset after Enter Fortress at 3:00.");
+    assert_eq!(run_end.messages.len(), 2);
+    assert_eq!(run_end.ping_window, Some(Duration::from_secs(300)));
+    assert_eq!(run_data.sent.get(&guild_id).map(Vec::len), Some(2));
+
+    response
+        .event_list
+        .insert(3, event(EventId::RsgCredits, 190000));
+    assert!(handle_reset_event(&response, &last_event, guild_id, &guild_data, &run_data).is_none());
+
+    // Only the messages that were ended stop being tracked.
+    let world_id = String::from("world");
+    let mut cache_manager = CacheManager::new(Database::open_in_memory()?);
+    cache_manager.runs.insert(world_id.to_owned(), run_data);
+    cache_manager.untrack_ended_messages(&world_id, guild_id, &[MessageId(1)]);
+    let run_data = cache_manager.runs.get(&world_id).unwrap();
+    assert_eq!(run_data.sent[&guild_id][0].message_id, MessageId(2));
+    assert!(run_data.messages.is_empty());
+    cache_manager.untrack_ended_messages(&world_id, guild_id, &[MessageId(2)]);
+    assert!(cache_manager.runs[&world_id].sent.is_empty());
+    Ok(())
+}
+
+#[test]
+pub fn test_item_requirements() -> Result<(), Box<dyn std::error::Error>> {
+    let db = Database::open_in_memory()?;
+    let guild_id = GuildId(1);
+    let mut requirement = ItemRequirement {
+        target: RequirementTarget::Split(Split::Blind),
+        item: ItemRequirement::item_from_id("minecraft:blaze_rod"),
+        min_count: 7,
+    };
+    assert_eq!(requirement.item, Item::MinecraftBlazeRod);
+    db.upsert_item_requirement(guild_id, &requirement)?;
+    requirement.min_count = 8;
+    db.upsert_item_requirement(guild_id, &requirement)?;
+    let role_requirement = ItemRequirement {
+        target: RequirementTarget::Role(RoleId(42)),
+        item: Item::MinecraftEnderPearl,
+        min_count: 12,
+    };
+    db.upsert_item_requirement(guild_id, &role_requirement)?;
+    let requirements = db.get_item_requirements(guild_id)?;
+    assert_eq!(requirements.len(), 2);
+    assert!(requirements.contains(&requirement) && requirements.contains(&role_requirement));
+    assert!(db.remove_item_requirement(guild_id, &role_requirement)?);
+    assert!(!db.remove_item_requirement(guild_id, &role_requirement)?);
+
+    let item_data = ItemData {
+        estimated_counts: HashMap::from([(Item::MinecraftBlazeRod, 8)]),
+        _usages: None,
+    };
+    assert!(requirement.is_met(Some(&item_data)));
+    requirement.min_count = 9;
+    assert!(!requirement.is_met(Some(&item_data)));
+    assert!(!role_requirement.is_met(Some(&item_data)));
+    let zero_requirement = ItemRequirement {
+        min_count: 0,
+        ..role_requirement.clone()
+    };
+    assert!(zero_requirement.is_met(Some(&item_data)));
+    assert!(!requirement.is_met(None));
+    Ok(())
+}
+
+#[test]
+pub fn test_rules() -> Result<(), Box<dyn std::error::Error>> {
+    let mut response = Response {
+        user: User {
+            uuid: "uuid".to_string(),
+            live_account: Some("runner".to_string()),
+        },
+        item_data: Some(ItemData {
+            estimated_counts: HashMap::from([(Item::MinecraftBlazeRod, 7)]),
+            _usages: None,
+        }),
+        nickname: "Runner".to_string(),
+        ..response(vec![
+            event(EventId::RsgEnterNether, 60000),
+            event(EventId::RsgEnterFortress, 140000),
+            event(EventId::RsgFirstPortal, 340000),
+        ])
+    };
+    let rule = parse_rule(
+        "blind < 6:00 and first_structure < 2:30 and bastionless and rods >= 7 -> <@&42>",
+    )?;
+    assert_eq!(rule.role_id, RoleId(42));
+    assert!(!rule.matches(&response, &response.event_list[1]));
+    assert!(rule.matches(&response, &response.event_list[2]));
+
+    let rule = parse_rule("(runner == runner or not live) and pearls > 0 -> <@&42>")?;
+    assert!(!rule.matches(&response, &response.event_list[2]));
+    let not_rule = parse_rule("not pearls > 0 -> <@&42>")?;
+    assert!(not_rule.matches(&response, &response.event_list[2]));
+    response.item_data = None;
+    assert!(!rule.matches(&response, &response.event_list[2]));
+    assert!(!not_rule.matches(&response, &response.event_list[2]));
+    assert!(parse_rule("nether < 2 -> <@&42>")?.matches(&response, &response.event_list[0]));
+
+    assert!(parse_rule("blind < 6:00").is_err());
+    assert!(parse_rule("blind < 6:75 -> <@&42>").is_err());
+    assert!(parse_rule("rods < 6:00 -> <@&42>").is_err());
+    assert!(parse_rule("runner < abc -> <@&42>").is_err());
+    assert!(parse_rule("bastion < 2 -> <@&42>").is_err());
+    Ok(())
+}
+
+#[test]
+pub fn test_channel_kinds() {
+    for kind in ChannelKind::ALL {
+        let param = kind.setting_key().replace("_channel", "");
+        assert_eq!(ChannelKind::from_command_param(&param), Some(kind));
+    }
+    assert_eq!(ChannelKind::from_command_param("pace_channel"), None);
+    assert_eq!(ChannelKind::from_command_param("Pace"), None);
+
+    assert!(is_audited("configure_channels", None));
+    assert!(is_audited("leaderboard", Some("set")));
+    assert!(!is_audited("leaderboard", Some("show")));
+    assert!(!is_audited("rules", Some("list")));
+    assert!(!is_audited("routes", Some("list")));
+    assert!(!is_audited("embed_template", Some("preview")));
+    assert!(!is_audited("validate_config", None));
+}
+
+#[test]
+pub fn test_routes() -> Result<(), Box<dyn std::error::Error>> {
+    let db = Database::open_in_memory()?;
+    let guild_id = GuildId(1);
+    let mut route = Route {
+        id: 0,
+        channel_id: ChannelId(2),
+        split: Some(Split::Finish),
+        runner: None,
+        run_type: Some(RunType::Bastionless),
+    };
+    route.id = db.add_route(guild_id, &route)?;
+    let runner_route = Route {
+        id: 0,
+        channel_id: ChannelId(3),
+        split: None,
+        runner: Some("Runner".to_string()),
+        run_type: None,
+    };
+    db.add_route(guild_id, &runner_route)?;
+    let routes = db.get_routes(guild_id)?;
+    assert_eq!(routes.len(), 2);
+    assert_eq!(routes[0], route);
+    assert!(routes[1].matches(&Split::Blind, "runner", &RunType::Modern));
+    assert!(!routes[1].matches(&Split::Blind, "other", &RunType::Modern));
+    assert!(route.matches(&Split::Finish, "other", &RunType::Bastionless));
+    assert!(!route.matches(&Split::Finish, "other", &RunType::Modern));
+    assert!(db.remove_route(guild_id, route.id)?);
+    assert!(!db.remove_route(guild_id, route.id)?);
+
+    let response = response(vec![
+        event(EventId::RsgEnterNether, 60000),
+        event(EventId::RsgEnterFortress, 140000),
+        event(EventId::RsgFirstPortal, 340000),
+        event(EventId::RsgCredits, 700000),
+    ]);
+    assert_eq!(
+        get_run_type(&response, &response.event_list[1]),
+        RunType::Modern
+    );
+    assert_eq!(
+        get_run_type(&response, &response.event_list[3]),
+        RunType::Bastionless
+    );
+    Ok(())
+}
+
+#[test]
+pub fn test_subscriptions() -> Result<(), Box<dyn std::error::Error>> {
+    let db = Database::open_in_memory()?;
+    let guild_id = GuildId(1);
+    let mut subscription = Subscription {
+        id: 0,
+        user_id: UserId(7),
+        runner: "Runner".to_string(),
+        split: Split::Blind,
+        time: 360000,
+    };
+    subscription.id = db.add_subscription(guild_id, &subscription)?;
+    assert_eq!(db.get_subscriptions(guild_id)?, vec![subscription.clone()]);
+    assert!(subscription.matches("runner", &Split::Blind, 359000));
+    assert!(!subscription.matches("runner", &Split::Blind, 360000));
+    assert!(!subscription.matches("runner", &Split::EyeSpy, 300000));
+    assert_eq!(db.remove_subscription(UserId(8), subscription.id)?, None);
+    assert_eq!(
+        db.remove_subscription(UserId(7), subscription.id)?,
+        Some(guild_id)
+    );
+    assert!(db.get_subscriptions(guild_id)?.is_empty());
+
+    let mut cache_manager = CacheManager::new(db);
+    for _ in 0..DM_RATE_LIMIT_COUNT {
+        assert!(cache_manager.allow_dm(UserId(7)));
+        cache_manager.record_dm(UserId(7));
+    }
+    assert!(!cache_manager.allow_dm(UserId(7)));
+    assert!(cache_manager.allow_dm(UserId(8)));
+    Ok(())
+}
+
+#[test]
+pub fn test_embed_template() -> Result<(), Box<dyn std::error::Error>> {
+    let template = EmbedTemplate::from_json(
+        r##"{"pace_title": "{igt} {split} by {runner}", "colours": {"blind": "#ff8800", "default": "#000001"}}"##,
+    )?;
+    assert_eq!(template.finish_title, EmbedTemplate::default().finish_title);
+    assert_eq!(template.colour(&Split::Blind), Some(0xff8800));
+    assert_eq!(template.colour(&Split::Finish), Some(1));
+    let values = HashMap::from([
+        ("igt", "5:30".to_string()),
+        ("split", "First Portal".to_string()),
+        ("runner", "Runner".to_string()),
+        (
+            "stats_link",
+            "https://paceman.gg/stats/run/world".to_string(),
+        ),
+        ("timestamp", "0".to_string()),
+        ("items", String::new()),
+        ("bastionless", "Yes".to_string()),
+    ]);
+    assert_eq!(
+        EmbedTemplate::render(&template.pace_title, &values),
+        "5:30 First Portal by Runner"
+    );
+    let fields = EmbedTemplate::render_fields(&template.pace_fields, &values);
+    assert_eq!(
+        fields
+            .iter()
+            .map(|(name, _, _)| name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Splits", "Time", "Bastionless"]
+    );
+
+    assert!(EmbedTemplate::from_json(r#"{"pace_title": "{unknown}"}"#).is_err());
+    assert!(EmbedTemplate::from_json(r#"{"pace_title": "{igt"}"#).is_err());
+    assert!(EmbedTemplate::from_json(r#"{"colours": {"blind": "orange"}}"#).is_err());
+    assert!(EmbedTemplate::from_json(r##"{"colours": {"bastion": "#ff8800"}}"##).is_err());
+    assert!(EmbedTemplate::from_json(r#"{"title": "{igt}"}"#).is_err());
+    assert!(EmbedTemplate::from_json(r#"{"finish_title": " "}"#).is_err());
+    assert!(EmbedTemplate::from_json(r#"{"pace_title": "{live} {items}"}"#).is_err());
+    assert!(EmbedTemplate::from_json(r#"{"pace_title": "{igt}"}"#).is_ok());
+    let long_title = format!(r#"{{"pace_title": "{} {{runner}}"}}"#, "x".repeat(240));
+    assert!(EmbedTemplate::from_json(&long_title).is_err());
+    let fields = |count: usize, value: &str| {
+        format!(
+            r#"{{"finish_fields": [{}]}}"#,
+            vec![format!(r#"{{"name": "Field", "value": "{}"}}"#, value); count].join(", ")
+        )
+    };
+    assert!(EmbedTemplate::from_json(&fields(17, "{igt}")).is_ok());
+    assert!(EmbedTemplate::from_json(&fields(18, "{igt}")).is_err());
+    assert!(EmbedTemplate::from_json(&fields(6, &"x".repeat(1000))).is_err());
+    EmbedTemplate::default().validate()?;
+    Ok(())
+}
+
+#[test]
+pub fn test_leaderboard() -> Result<(), Box<dyn std::error::Error>> {
+    let db = Database::open_in_memory()?;
+    let guild_id = GuildId(1);
+    let entry = |uuid: &str, runner: &str, split: Split, time: u64| LeaderboardEntry {
+        uuid: uuid.to_string(),
+        runner: runner.to_string(),
+        split,
+        time,
+        world_id: Some(format!("{}-{}", runner, time)),
+        date: 1700000000,
+    };
+    assert!(db.add_leaderboard_run(
+        guild_id,
+        &entry("a", "Runner_A", Split::Finish, 540000),
+        None
+    )?);
+    assert!(db.add_leaderboard_run(
+        guild_id,
+        &entry("b", "RunnerB", Split::Finish, 500000),
+        None
+    )?);
+    assert!(!db.add_leaderboard_run(
+        guild_id,
+        &entry("a", "Runner_A", Split::Finish, 600000),
+        None
+    )?);
+    assert!(db.add_leaderboard_run(
+        guild_id,
+        &entry("a", "Renamed", Split::Blind, 300000),
+        None
+    )?);
+    assert!(db.add_leaderboard_run(
+        GuildId(2),
+        &entry("a", "Runner_A", Split::Finish, 400000),
+        None
+    )?);
+
+    let leaderboard = db.get_leaderboard(guild_id, None)?;
+    let finishes = leaderboard.finishes();
+    assert_eq!(finishes.len(), 2);
+    assert_eq!(finishes[0].runner, "RunnerB");
+    assert_eq!(finishes[1].runner, "Renamed");
+    assert_eq!(finishes[1].time, 540000);
+    assert_eq!(finishes[1].world_id.as_deref(), Some("Runner_A-540000"));
+    assert_eq!(
+        leaderboard.render(LEADERBOARD_HEADER),
+        vec!["## Runner Leaderboard\n\n\
+        `8:20`\t\tRunnerB\t\t<t:1700000000:d>\t\t[Run](https://paceman.gg/stats/run/RunnerB-500000)\n\
+        `9:00`\t\tRenamed\t\t<t:1700000000:d>\t\t[Run](https://paceman.gg/stats/run/Runner_A-540000)\n\
+        \n### Best Splits\nBlind: `5:00` Renamed"]
+    );
+
+    for idx in 0..40 {
+        let uuid = format!("runner-{}", idx);
+        db.add_leaderboard_run(
+            guild_id,
+            &entry(&uuid, &uuid, Split::Finish, 600000 + idx),
+            None,
+        )?;
+    }
+    let pages = db
+        .get_leaderboard(guild_id, None)?
+        .render(LEADERBOARD_HEADER);
+    assert!(pages.len() > 1);
+    assert!(pages
+        .iter()
+        .all(|page| page.len() <= LEADERBOARD_PAGE_LIMIT));
+    assert!(pages[0].starts_with("## Runner Leaderboard"));
+    assert!(pages.last().unwrap().ends_with("Blind: `5:00` Renamed"));
+    assert_eq!(
+        pages
+            .iter()
+            .map(|page| page.matches("[Run]").count())
+            .sum::<usize>(),
+        42
+    );
+
+    let (uuid, name) = db.find_leaderboard_runner(guild_id, "renamed")?.unwrap();
+    assert_eq!((uuid.as_str(), name.as_str()), ("a", "Renamed"));
+    assert_eq!(db.find_leaderboard_runner(guild_id, "Runner_A")?, None);
+    assert!(db
+        .get_leaderboard(guild_id, None)?
+        .render_runner(&uuid)
+        .unwrap()
+        .contains("Blind: `5:00`"));
+    assert_eq!(
+        db.set_leaderboard_best(
+            guild_id,
+            &LeaderboardEntry {
+                date: parse_date("2024-02-14")?,
+                ..entry(&uuid, &name, Split::Blind, 290000)
+            }
+        )?,
+        0
+    );
+    assert_eq!(
+        db.set_leaderboard_best(guild_id, &entry(&uuid, &name, Split::Blind, 295000))?,
+        1
+    );
+    let leaderboard = db.get_leaderboard(guild_id, None)?;
+    assert_eq!(leaderboard.best(&Split::Blind).unwrap().time, 295000);
+    assert_eq!(
+        db.get_leaderboard(guild_id, Some((1707868800, 1707955200)))?
+            .bests
+            .len(),
+        0
+    );
+    assert_eq!(
+        db.remove_leaderboard_runs(guild_id, &uuid, Some(&Split::Blind))?,
+        2
+    );
+    db.add_leaderboard_run(guild_id, &entry(&uuid, &name, Split::Blind, 300000), None)?;
+    assert_eq!(
+        db.remove_leaderboard_runs(guild_id, &uuid, Some(&Split::Finish))?,
+        2
+    );
+    let leaderboard = db.get_leaderboard(guild_id, None)?;
+    assert_eq!(leaderboard.finishes().len(), 41);
+    assert!(leaderboard.best(&Split::Blind).is_some());
+    assert_eq!(db.reset_leaderboard(guild_id)?, 42);
+    assert!(db.get_leaderboard(guild_id, None)?.bests.is_empty());
+    assert_eq!(db.get_leaderboard(GuildId(2), None)?.bests.len(), 1);
+
+    let imported = parse_leaderboard_message(
+        "## Runner Leaderboard\n\n`8:20`\t\tRunnerˍC\n`bad`\t\tRunnerD\n\
+        `9:00`\t\tRunnerE\t\t<t:1700000000:d>",
+    );
+    assert_eq!(imported, vec![("Runner_C".to_string(), 500000)]);
+    db.add_leaderboard_run(
+        guild_id,
+        &LeaderboardEntry {
+            world_id: None,
+            ..entry("name:runner_c", "Runner_C", Split::Finish, 500000)
+        },
+        None,
+    )?;
+    // The imported time is handed over once the runner shows up with their uuid.
+    assert!(!db.add_leaderboard_run(
+        guild_id,
+        &entry("c", "Runner_C", Split::Finish, 510000),
+        None
+    )?);
+    let leaderboard = db.get_leaderboard(guild_id, None)?;
+    assert_eq!(leaderboard.bests.len(), 1);
+    assert_eq!(leaderboard.bests[0].uuid, "c");
+    assert_eq!(leaderboard.bests[0].time, 500000);
+    Ok(())
+}
+
+#[test]
+pub fn test_leaderboard_period() -> Result<(), Box<dyn std::error::Error>> {
+    // 2024-02-14 12:00 UTC is a Wednesday.
+    let time = 1707912000;
+    assert_eq!(
+        LeaderboardPeriod::Weekly.bounds(time),
+        Some((1707696000, 1708300800))
+    );
+    assert_eq!(
+        LeaderboardPeriod::Monthly.bounds(time),
+        Some((1706745600, 1709251200))
+    );
+    let season = LeaderboardPeriod::season("2024-02-01", "2024-02-29").unwrap();
+    assert_eq!(
+        season,
+        LeaderboardPeriod::Season {
+            start: 1706745600,
+            end: 1709251200
+        }
+    );
+    assert_eq!(season.bounds(1709251200), None);
+    assert_eq!(
+        season.ending_after(1700000000),
+        Some((1706745600, 1709251200))
+    );
+    assert!(LeaderboardPeriod::season("2024-02-29", "2024-02-01").is_none());
+    assert_eq!(
+        LeaderboardPeriod::from_setting(season.to_setting().as_str()),
+        Some(season)
+    );
+
+    let db = Database::open_in_memory()?;
+    let guild_id = GuildId(1);
+    let period = LeaderboardPeriod::Weekly;
+    assert_eq!(get_rollover(&db, guild_id, &period, time)?, None);
+    let entry = LeaderboardEntry {
+        uuid: "a".to_string(),
+        runner: "Runner".to_string(),
+        split: Split::Finish,
+        time: 540000,
+        world_id: None,
+        date: time,
+    };
+    assert!(db.add_leaderboard_run(guild_id, &entry, period.bounds(time).map(|b| b.0))?);
+    assert_eq!(get_rollover(&db, guild_id, &period, time + 3600)?, None);
+    // Three weeks later, only the week with a completion is archived.
+    let rollover = get_rollover(&db, guild_id, &period, time + 21 * 86400)?.unwrap();
+    let (archives, pages) = (&rollover.archives, &rollover.pages);
+    assert_eq!(archives.len(), 1);
+    assert!(archives[0].starts_with(LEADERBOARD_ARCHIVE_HEADER));
+    assert!(archives[0].contains("1. `9:00`\t\tRunner"));
+    assert_eq!(pages.len(), 2);
+    assert!(pages[0].ends_with("No completions yet."));
+    assert!(pages[1].contains("`9:00`\t\tRunner"));
+    // Nothing is saved until the archives are sent, so a failed rollover is retried.
+    assert!(get_rollover(&db, guild_id, &period, time + 21 * 86400)?.is_some());
+    db.set_setting(
+        guild_id,
+        LEADERBOARD_ROLLOVER_SETTING,
+        &rollover.since.to_string(),
+    )?;
+    assert_eq!(
+        get_rollover(&db, guild_id, &period, time + 21 * 86400)?,
+        None
+    );
+    Ok(())
+}
+
+#[test]
+pub fn test_runner_uuids() -> Result<(), Box<dyn std::error::Error>> {
+    let uuid = format_uuid("0123456789ABCDEF0123456789abcdef");
+    assert_eq!(uuid, "01234567-89ab-cdef-0123-456789abcdef");
+    assert_eq!(format_uuid(&uuid), uuid);
+
+    let db = Database::open_in_memory()?;
+    let guild_id = GuildId(1);
+    let mut splits = PlayerSplitsData::default();
+    db.upsert_player(guild_id, "OldName", None, &splits)?;
+    let role = serde_json::from_value(serde_json::json!({
+        "id": "10",
+        "guild_id": "1",
+        "name": "*B4:00+OldName",
+        "color": 0,
+        "hoist": false,
+        "managed": false,
+        "mentionable": true,
+        "permissions": "0",
+        "position": 1,
+    }))?;
+    let mut guild_data = GuildData {
+        config_channel: Some(ChannelId(2)),
+        players: db.get_players(guild_id)?,
+        runner_uuids: db.get_runner_uuids(guild_id)?,
+        roles: vec![RoleData::new(role)?],
+        is_private: true,
+        ..guild_data()
+    };
+    let mut response = Response {
+        user: User {
+            uuid: uuid.clone(),
+            live_account: None,
+        },
+        nickname: "oldname".to_string(),
+        ..response(vec![])
+    };
+    // The first run links the runner to their account.
+    assert!(handle_runner_rename(&db, guild_id, &mut guild_data, &response).is_none());
+    assert_eq!(db.get_runner_uuids(guild_id)?, guild_data.runner_uuids);
+    assert_eq!(
+        guild_data.runner_uuids.get(&uuid).map(|n| n.as_str()),
+        Some("oldname")
+    );
+    let pings = |guild_data: &GuildData, response: &Response| {
+        get_roles_to_ping(guild_data, &splits, &Split::Blind, 200000, response).len()
+    };
+    assert_eq!(pings(&guild_data, &response), 1);
+    let impostor = Response {
+        user: User {
+            uuid: "other".to_string(),
+            live_account: None,
+        },
+        ..response.clone()
+    };
+    assert_eq!(pings(&guild_data, &impostor), 0);
+
+    response.nickname = "NewName".to_string();
+    let rename = handle_runner_rename(&db, guild_id, &mut guild_data, &response).unwrap();
+    assert_eq!(
+        (rename.old_name.as_str(), rename.new_name.as_str()),
+        ("oldname", "NewName")
+    );
+    assert_eq!(
+        rename.roles,
+        vec![(RoleId(10), "*B4:00+NewName".to_string())]
+    );
+    assert_eq!(guild_data.roles[0].runner, "NewName");
+    // The renamed runner keeps their role before Discord sends the updated one.
+    assert_eq!(pings(&guild_data, &response), 1);
+    assert!(guild_data.find_player(&uuid, "NewName").is_some());
+    assert!(!guild_data.players.contains_key("oldname"));
+    assert!(db.get_players(guild_id)?.contains_key("newname"));
+    // Someone else using the old name is not treated as the runner.
+    assert!(guild_data.find_player("other", "NewName").is_none());
+
+    // Adding the runner again under another name keeps a single entry.
+    splits.blind = 300000;
+    db.upsert_player(guild_id, "ThirdName", Some(uuid.as_str()), &splits)?;
+    let players = db.get_players(guild_id)?;
+    assert_eq!(players.len(), 1);
+    assert_eq!(players.get("thirdname"), Some(&splits));
     Ok(())
 }
@@ -1,3 +1,6 @@
 use serenity::utils::Colour;
 
 pub const ROLE_COLOR: u32 = Colour::from_rgb(54, 57, 63).0;
+pub const MAX_ACTION_ROWS: usize = 5;
+pub const MOJANG_PROFILE_URL: &str = "https://api.mojang.com/users/profiles/minecraft";
+pub const MOJANG_TIMEOUT_SECS: u64 = 5;
@@ -1,13 +1,15 @@
 use crate::{cache::players::PlayerSplitsData, Result};
 
+use super::parse_time::parse_time;
+
 pub fn extract_name_and_splits_from_line(line: &str) -> Result<(String, PlayerSplitsData)> {
     let line = line.trim();
     let line = line.replace(" ", "");
-    let line_splits = line.split(':').collect::<Vec<&str>>();
-    if line_splits.len() != 2 {
-        return Err(format!("ExtractError: parse line contents: '{}'.", line).into());
-    }
-    let (player_name, splits_string) = (line_splits[0], line_splits[1]);
+    // Split times can contain ':' themselves, so only the first one separates the name.
+    let (player_name, splits_string) = match line.split_once(':') {
+        Some(parts) => parts,
+        None => return Err(format!("ExtractError: parse line contents: '{}'.", line).into()),
+    };
     let splits = splits_string.split('/').collect::<Vec<&str>>();
     if splits.len() != 5 && splits.len() != 6 {
         return Err(format!("ExtractError: parse line contents: '{}'.", line).into());
@@ -15,19 +17,19 @@ pub fn extract_name_and_splits_from_line(line: &str) -> Result<(String, PlayerSp
     let mut idx = 0;
     let mut split_data = PlayerSplitsData::default();
     for split in splits {
-        let split_u8 = match split.parse::<u8>() {
+        let split_millis = match parse_time(split) {
             Ok(split) => split,
             Err(err) => {
-                return Err(format!("ExtractError: parse to u8 due to: {}", err).into());
+                return Err(format!("ExtractError: parse split time due to: {}", err).into());
             }
         };
         match idx {
-            0 => split_data.first_structure = split_u8,
-            1 => split_data.second_structure = split_u8,
-            2 => split_data.blind = split_u8,
-            3 => split_data.eye_spy = split_u8,
-            4 => split_data.end_enter = split_u8,
-            5 => split_data.finish = Some(split_u8),
+            0 => split_data.first_structure = split_millis,
+            1 => split_data.second_structure = split_millis,
+            2 => split_data.blind = split_millis,
+            3 => split_data.eye_spy = split_millis,
+            4 => split_data.end_enter = split_millis,
+            5 => split_data.finish = Some(split_millis),
             _ => (),
         };
         idx += 1;
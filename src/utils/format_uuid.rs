@@ -0,0 +1,16 @@
+// Mojang leaves the dashes out of uuids while paceman.gg keeps them, so every uuid
+// is stored in the dashed form.
+pub fn format_uuid(uuid: &str) -> String {
+    let uuid = uuid.trim().to_lowercase();
+    if uuid.len() != 32 || !uuid.chars().all(|c| c.is_ascii_hexdigit()) {
+        return uuid;
+    }
+    format!(
+        "{}-{}-{}-{}-{}",
+        &uuid[0..8],
+        &uuid[8..12],
+        &uuid[12..16],
+        &uuid[16..20],
+        &uuid[20..32]
+    )
+}
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use crate::Result;
+
+use super::{
+    consts::{MOJANG_PROFILE_URL, MOJANG_TIMEOUT_SECS},
+    format_uuid::format_uuid,
+};
+
+#[derive(Deserialize)]
+struct Profile {
+    id: String,
+    name: String,
+}
+
+// Returns the uuid and the correctly capitalized name of a Minecraft account, or
+// nothing if no account has that name.
+pub async fn get_minecraft_profile(name: &str) -> Result<Option<(String, String)>> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(MOJANG_TIMEOUT_SECS))
+        .build()?;
+    let response = client
+        .get(format!("{}/{}", MOJANG_PROFILE_URL, name))
+        .send()
+        .await?;
+    match response.status() {
+        StatusCode::NOT_FOUND | StatusCode::NO_CONTENT => return Ok(None),
+        status if !status.is_success() => {
+            return Err(format!(
+                "MinecraftProfileError: look up name: '{}' with status: {}",
+                name, status
+            )
+            .into())
+        }
+        _ => (),
+    };
+    let profile: Profile = response.json().await?;
+    Ok(Some((format_uuid(&profile.id), profile.name)))
+}
@@ -0,0 +1,37 @@
+use serenity::{
+    client::Context,
+    model::id::{ChannelId, GuildId},
+};
+
+use crate::{cache::CacheManager, handler::ArcMutex, Result};
+
+use super::extract_name_and_splits_from_line::extract_name_and_splits_from_line;
+
+// The message is fetched before the cache manager is locked.
+pub async fn import_config_message(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    cache_manager: &ArcMutex<CacheManager>,
+) -> Result<usize> {
+    let messages = channel_id.messages(&ctx.http, |m| m.limit(1)).await?;
+    let config_message = match messages.last() {
+        Some(msg) => msg,
+        None => return Ok(0),
+    };
+    let mut runners = vec![];
+    for line in config_message.content.split("\n") {
+        let line = line.trim();
+        if line.starts_with("```") || line.is_empty() {
+            continue;
+        }
+        runners.push(extract_name_and_splits_from_line(line)?);
+    }
+    let locked_cache_manager = cache_manager.lock().await;
+    for (name, splits) in runners.iter() {
+        locked_cache_manager
+            .db
+            .upsert_player(guild_id, name.as_str(), None, splits)?;
+    }
+    Ok(runners.len())
+}
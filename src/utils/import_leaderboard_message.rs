@@ -0,0 +1,76 @@
+use serenity::{
+    client::Context,
+    model::id::{ChannelId, GuildId},
+};
+
+use crate::{
+    cache::{
+        leaderboard::{LeaderboardEntry, LEADERBOARD_HEADER},
+        split::Split,
+        CacheManager,
+    },
+    db::consts::LEADERBOARD_LEGACY_UUID_PREFIX,
+    dispatcher::consts::SPECIAL_UNDERSCORE,
+    handler::ArcMutex,
+    Result,
+};
+
+use super::parse_time::parse_time;
+
+// The old leaderboard only had lines like "`9:30`\t\tname". Lines that don't look like
+// that are skipped.
+pub fn parse_leaderboard_message(content: &str) -> Vec<(String, u64)> {
+    content
+        .split('\n')
+        .filter_map(|line| {
+            let (time, name) = line.trim().split_once("\t\t")?;
+            let name = name.trim().replace(SPECIAL_UNDERSCORE, "_");
+            if name.is_empty() || name.contains("\t\t") {
+                return None;
+            }
+            let time = parse_time(time.trim().trim_matches('`')).ok()?;
+            Some((name, time))
+        })
+        .collect()
+}
+
+// Imports the finishes of the leaderboard message from before the database. Runners are
+// matched to the uuid they are whitelisted with, the others keep a placeholder until
+// their next run. The runs are dated to when the message was first sent.
+pub async fn import_leaderboard_message(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    cache_manager: &ArcMutex<CacheManager>,
+) -> Result<usize> {
+    let messages = channel_id.messages(&ctx.http, |m| m.limit(100)).await?;
+    let runs = messages
+        .iter()
+        .filter(|m| m.content.starts_with(LEADERBOARD_HEADER))
+        .flat_map(|m| {
+            parse_leaderboard_message(m.content.as_str())
+                .into_iter()
+                .map(|(runner, time)| (runner, time, m.timestamp.unix_timestamp()))
+        })
+        .collect::<Vec<_>>();
+    let locked_cache_manager = cache_manager.lock().await;
+    let db = &locked_cache_manager.db;
+    let runner_uuids = db.get_runner_uuids(guild_id)?;
+    for (runner, time, date) in runs.iter() {
+        let name = runner.to_lowercase();
+        let uuid = match runner_uuids.iter().find(|(_, linked)| *linked == &name) {
+            Some((uuid, _)) => uuid.to_owned(),
+            None => format!("{}{}", LEADERBOARD_LEGACY_UUID_PREFIX, name),
+        };
+        let entry = LeaderboardEntry {
+            uuid,
+            runner: runner.to_owned(),
+            split: Split::Finish,
+            time: *time,
+            world_id: None,
+            date: *date,
+        };
+        db.add_leaderboard_run(guild_id, &entry, None)?;
+    }
+    Ok(runs.len())
+}
@@ -0,0 +1,86 @@
+use serenity::{client::Context, model::id::GuildId};
+
+use crate::{
+    cache::{channel_kind::ChannelKind, CacheManager},
+    db::consts::{CONFIG_IMPORTED_SETTING, LEADERBOARD_IMPORTED_SETTING},
+    handler::ArcMutex,
+    Result,
+};
+
+use super::{
+    import_config_message::import_config_message,
+    import_leaderboard_message::import_leaderboard_message,
+};
+
+// The messages the bot was configured with before it had a database are imported once,
+// before the guild is first cached. They are fetched without holding the cache manager
+// lock, so the dispatcher keeps running meanwhile.
+pub async fn import_legacy_messages(
+    ctx: &Context,
+    guild_id: GuildId,
+    cache_manager: &ArcMutex<CacheManager>,
+) -> Result<()> {
+    let (config_channel, lb_channel) = {
+        let locked_cache_manager = cache_manager.lock().await;
+        let db = &locked_cache_manager.db;
+        let config_channel = ChannelKind::Config.resolve(ctx, guild_id, db)?;
+        let config_imported = db.get_setting(guild_id, CONFIG_IMPORTED_SETTING)?.is_some();
+        let lb_channel = ChannelKind::Leaderboard.resolve(ctx, guild_id, db)?;
+        let lb_imported = db
+            .get_setting(guild_id, LEADERBOARD_IMPORTED_SETTING)?
+            .is_some();
+        (
+            config_channel.filter(|_| !config_imported),
+            lb_channel.filter(|_| config_channel.is_some() && !lb_imported),
+        )
+    };
+    if let Some(players_channel) = config_channel {
+        let imported = match import_config_message(ctx, guild_id, players_channel, cache_manager)
+            .await
+            .map_err(|err| err.to_string())
+        {
+            Ok(imported) => imported,
+            Err(err) => {
+                return Err(format!(
+                    "ImportLegacyMessagesError: import #pacemanbot-runner-names in guild id: {} due to: {}",
+                    guild_id, err
+                )
+                .into())
+            }
+        };
+        println!(
+            "Imported {} runners from #pacemanbot-runner-names in guild id: {}.",
+            imported, guild_id
+        );
+        cache_manager
+            .lock()
+            .await
+            .db
+            .set_setting(guild_id, CONFIG_IMPORTED_SETTING, "true")?;
+    }
+    // The old leaderboard is overwritten on the next update, so a failed import is
+    // retried the next time the guild is loaded instead of failing the guild.
+    if let Some(lb_channel) = lb_channel {
+        match import_leaderboard_message(ctx, guild_id, lb_channel, cache_manager)
+            .await
+            .map_err(|err| err.to_string())
+        {
+            Ok(imported) => {
+                println!(
+                    "Imported {} runs from #pacemanbot-runner-leaderboard in guild id: {}.",
+                    imported, guild_id
+                );
+                cache_manager.lock().await.db.set_setting(
+                    guild_id,
+                    LEADERBOARD_IMPORTED_SETTING,
+                    "true",
+                )?;
+            }
+            Err(err) => eprintln!(
+                "ImportLegacyMessagesError: import #pacemanbot-runner-leaderboard in guild id: {} due to: {}",
+                guild_id, err
+            ),
+        };
+    }
+    Ok(())
+}
@@ -1,4 +1,7 @@
-pub fn mins_secs_to_millis(time: (u8, u8)) -> u64 {
-    let (minutes, seconds) = (time.0 as u64, time.1 as u64);
-    minutes * 60000 + seconds * 1000
+// Returns None when the time doesn't fit in milliseconds.
+pub fn mins_secs_to_millis<T: Into<u64>>(time: (T, T)) -> Option<u64> {
+    let (minutes, seconds): (u64, u64) = (time.0.into(), time.1.into());
+    minutes
+        .checked_mul(60000)?
+        .checked_add(seconds.checked_mul(1000)?)
 }
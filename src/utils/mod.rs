@@ -6,10 +6,19 @@ pub mod extract_split_from_pb_role_name;
 pub mod extract_split_from_role_name;
 pub mod extract_splits_and_name_from_role_name;
 pub mod format_time;
+pub mod format_uuid;
 pub mod get_event_type;
-pub mod get_new_config_contents;
+pub mod get_minecraft_profile;
+pub mod import_config_message;
+pub mod import_leaderboard_message;
+pub mod import_legacy_messages;
 pub mod millis_to_mins_secs;
 pub mod mins_secs_to_millis;
+pub mod parse_date;
+pub mod parse_time;
 pub mod remove_roles_starting_with;
 pub mod remove_runner_pings;
+pub mod render_leaderboard;
+pub mod rollover_leaderboards;
+pub mod send_audit_log;
 pub mod update_leaderboard;
@@ -0,0 +1,15 @@
+use chrono::NaiveDate;
+
+use crate::Result;
+
+// Dates are given as 'YYYY-MM-DD' and point to the start of that day (UTC).
+pub fn parse_date(date: &str) -> Result<i64> {
+    let date = date.trim();
+    match NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+    {
+        Some(date) => Ok(date.and_utc().timestamp()),
+        None => Err(format!("ParseDateError: parse date: '{}'.", date).into()),
+    }
+}
@@ -0,0 +1,31 @@
+use crate::Result;
+
+use super::mins_secs_to_millis::mins_secs_to_millis;
+
+fn parse_number(number: &str, time: &str) -> Result<u64> {
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("ParseTimeError: parse number in time: '{}'.", time).into());
+    }
+    Ok(number.parse::<u64>()?)
+}
+
+// Times are either given in whole minutes ("10") or as minutes and seconds ("3:30").
+pub fn parse_time(time: &str) -> Result<u64> {
+    let time = time.trim();
+    let (minutes, seconds) = match time.split_once(':') {
+        Some((minutes, seconds)) => {
+            if seconds.len() != 2 {
+                return Err(format!("ParseTimeError: parse seconds in time: '{}'.", time).into());
+            }
+            (minutes, parse_number(seconds, time)?)
+        }
+        None => (time, 0),
+    };
+    if seconds >= 60 {
+        return Err(format!("ParseTimeError: seconds out of range in time: '{}'.", time).into());
+    }
+    match mins_secs_to_millis((parse_number(minutes, time)?, seconds)) {
+        Some(millis) => Ok(millis),
+        None => Err(format!("ParseTimeError: time out of range: '{}'.", time).into()),
+    }
+}
@@ -0,0 +1,57 @@
+use serenity::model::id::{ChannelId, GuildId};
+
+use crate::{
+    cache::{
+        leaderboard::{LeaderboardPeriod, LEADERBOARD_HEADER},
+        CacheManager,
+    },
+    db::Database,
+    Result,
+};
+
+// The board of the current period comes first, followed by the all-time board.
+pub fn render_leaderboard(
+    db: &Database,
+    guild_id: GuildId,
+    period: Option<&LeaderboardPeriod>,
+    now: i64,
+) -> Result<Vec<String>> {
+    let mut pages = vec![];
+    if let Some((period, bounds)) = period.and_then(|period| Some((period, period.bounds(now)?))) {
+        pages.extend(
+            db.get_leaderboard(guild_id, Some(bounds))?
+                .render(&period.title(bounds)),
+        );
+    }
+    pages.extend(
+        db.get_leaderboard(guild_id, None)?
+            .render(LEADERBOARD_HEADER),
+    );
+    Ok(pages)
+}
+
+// Returns the leaderboard channel of a guild together with its pages, if the guild
+// has a leaderboard.
+pub fn render_guild_leaderboard(
+    cache_manager: &CacheManager,
+    guild_id: GuildId,
+    now: i64,
+) -> Option<(ChannelId, Vec<String>)> {
+    let guild_data = cache_manager.cache.get(&guild_id)?;
+    let channel_id = guild_data.lb_channel.filter(|_| guild_data.is_private)?;
+    match render_leaderboard(
+        &cache_manager.db,
+        guild_id,
+        guild_data.leaderboard_period.as_ref(),
+        now,
+    ) {
+        Ok(pages) => Some((channel_id, pages)),
+        Err(err) => {
+            eprintln!(
+                "RenderLeaderboardError: render leaderboard in guild name: {} due to: {}",
+                guild_data.name, err
+            );
+            None
+        }
+    }
+}
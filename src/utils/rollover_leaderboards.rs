@@ -0,0 +1,151 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serenity::{client::Context, model::id::GuildId};
+
+use crate::{
+    cache::{leaderboard::LeaderboardPeriod, CacheManager},
+    db::{consts::LEADERBOARD_ROLLOVER_SETTING, Database},
+    handler::ArcMutex,
+    Result,
+};
+
+use super::{
+    render_leaderboard::render_leaderboard,
+    update_leaderboard::{archive_leaderboard, update_leaderboard},
+};
+
+#[derive(Debug, PartialEq)]
+pub struct Rollover {
+    pub archives: Vec<String>,
+    pub pages: Vec<String>,
+    pub since: i64,
+}
+
+// Archives every period that ended since the last rollover. Periods without any
+// completions are skipped, but still start a fresh board. The end of the last archived
+// period is only saved once the archives are sent.
+pub fn get_rollover(
+    db: &Database,
+    guild_id: GuildId,
+    period: &LeaderboardPeriod,
+    now: i64,
+) -> Result<Option<Rollover>> {
+    let mut since = match db
+        .get_setting(guild_id, LEADERBOARD_ROLLOVER_SETTING)?
+        .and_then(|since| since.parse::<i64>().ok())
+    {
+        Some(since) => since,
+        None => {
+            db.set_setting(guild_id, LEADERBOARD_ROLLOVER_SETTING, &now.to_string())?;
+            return Ok(None);
+        }
+    };
+    let mut archives = vec![];
+    let mut rolled_over = false;
+    while let Some(bounds) = period.ending_after(since) {
+        if bounds.1 > now {
+            break;
+        }
+        let leaderboard = db.get_leaderboard(guild_id, Some(bounds))?;
+        if !leaderboard.finishes().is_empty() {
+            archives.push(leaderboard.render_archive(period, bounds));
+        }
+        since = bounds.1;
+        rolled_over = true;
+    }
+    if !rolled_over {
+        return Ok(None);
+    }
+    let pages = render_leaderboard(db, guild_id, Some(period), now)?;
+    Ok(Some(Rollover {
+        archives,
+        pages,
+        since,
+    }))
+}
+
+pub async fn rollover_leaderboards(ctx: &Context, cache_manager: &ArcMutex<CacheManager>) {
+    let now = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        Err(err) => return eprintln!("RolloverLeaderboardsError: get time due to: {}", err),
+    };
+    let guild_ids = cache_manager
+        .lock()
+        .await
+        .cache
+        .iter()
+        .filter(|(_, guild_data)| {
+            guild_data.is_private
+                && guild_data.lb_channel.is_some()
+                && guild_data.leaderboard_period.is_some()
+        })
+        .map(|(guild_id, _)| *guild_id)
+        .collect::<Vec<_>>();
+    // The rollover is worked out under the leaderboard lock of the guild, and the cache
+    // lock is released again before any of the Discord calls are made. A rollover whose
+    // results couldn't be sent is tried again on the next run.
+    for guild_id in guild_ids {
+        let leaderboard_lock = cache_manager.lock().await.leaderboard_lock(guild_id);
+        let _guard = leaderboard_lock.lock().await;
+        let rollover = {
+            let locked_cache_manager = cache_manager.lock().await;
+            let guild_data = match locked_cache_manager.cache.get(&guild_id) {
+                Some(guild_data) => guild_data,
+                None => continue,
+            };
+            let (channel_id, period) = match (guild_data.lb_channel, &guild_data.leaderboard_period)
+            {
+                (Some(channel_id), Some(period)) if guild_data.is_private => (channel_id, period),
+                _ => continue,
+            };
+            match get_rollover(&locked_cache_manager.db, guild_id, period, now) {
+                Ok(rollover) => {
+                    rollover.map(|rollover| (guild_data.name.to_owned(), channel_id, rollover))
+                }
+                Err(err) => {
+                    eprintln!(
+                        "RolloverLeaderboardsError: roll over leaderboard in guild name: {} due to: {}",
+                        guild_data.name, err
+                    );
+                    None
+                }
+            }
+        };
+        let (guild_name, channel_id, rollover) = match rollover {
+            Some(rollover) => rollover,
+            None => continue,
+        };
+        if let Err(err) = archive_leaderboard(ctx, channel_id, &rollover.archives)
+            .await
+            .map_err(|err| err.to_string())
+        {
+            eprintln!(
+                "RolloverLeaderboardsError: archive leaderboard in guild name: {} due to: {}",
+                guild_name, err
+            );
+            continue;
+        }
+        if let Err(err) = cache_manager.lock().await.db.set_setting(
+            guild_id,
+            LEADERBOARD_ROLLOVER_SETTING,
+            &rollover.since.to_string(),
+        ) {
+            eprintln!(
+                "RolloverLeaderboardsError: save rollover in guild name: {} due to: {}",
+                guild_name, err
+            );
+            continue;
+        }
+        match update_leaderboard(ctx, channel_id, &rollover.pages).await {
+            Ok(_) => println!(
+                "Rolled over leaderboard with {} archived periods in guild name: {}.",
+                rollover.archives.len(),
+                guild_name
+            ),
+            Err(err) => eprintln!(
+                "RolloverLeaderboardsError: update leaderboard in guild name: {} due to: {}",
+                guild_name, err
+            ),
+        };
+    }
+}
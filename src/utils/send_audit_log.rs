@@ -0,0 +1,68 @@
+use serenity::{
+    client::Context,
+    model::{id::GuildId, prelude::application_command::ApplicationCommandInteraction},
+    prelude::Mentionable,
+};
+
+use crate::{cache::CacheManager, handler::ArcMutex};
+
+// Only commands that change the configuration end up in the audit log, so
+// commands and actions that just show something are left out.
+pub fn is_audited(command_name: &str, action: Option<&str>) -> bool {
+    !matches!(
+        (command_name, action),
+        ("send_message" | "validate_config" | "subscribe", _)
+            | ("leaderboard", Some("show"))
+            | ("rules" | "routes", Some("list"))
+            | ("embed_template", Some("preview"))
+    )
+}
+
+pub async fn send_audit_log(
+    ctx: &Context,
+    guild_id: GuildId,
+    command: &ApplicationCommandInteraction,
+    cache_manager: ArcMutex<CacheManager>,
+) {
+    let audit_channel = match cache_manager.lock().await.cache.get(&guild_id) {
+        Some(guild_data) => guild_data.audit_channel,
+        None => None,
+    };
+    let audit_channel = match audit_channel {
+        Some(channel_id) => channel_id,
+        None => return,
+    };
+    let options = command
+        .data
+        .options
+        .iter()
+        .map(|option| {
+            let value = match &option.value {
+                Some(serde_json::Value::String(value)) => value.to_owned(),
+                Some(value) => value.to_string(),
+                None => String::new(),
+            };
+            format!("{}: `{}`", option.name, value)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let content = format!(
+        "{} used `/{}` {}",
+        command.user.mention(),
+        command.data.name,
+        options
+    );
+    match audit_channel
+        .send_message(ctx, |m| {
+            m.content(content.trim())
+                .allowed_mentions(|a| a.empty_parse())
+        })
+        .await
+    {
+        Ok(_) => (),
+        Err(err) => eprintln!(
+            "SendAuditLogError: send audit log in guild id: {} due to: {}",
+            guild_id, err
+        ),
+    };
+}
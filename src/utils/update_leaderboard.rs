@@ -1,73 +1,107 @@
-use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use serenity::{client::Context, model::id::ChannelId};
+use serenity::{
+    client::Context,
+    model::{
+        channel::Message,
+        id::{ChannelId, GuildId},
+    },
+};
 
-use crate::Result;
+use crate::{
+    cache::{leaderboard::LEADERBOARD_ARCHIVE_HEADER, CacheManager},
+    handler::ArcMutex,
+    Result,
+};
 
-use super::{format_time::format_time, mins_secs_to_millis::mins_secs_to_millis};
+use super::render_leaderboard::render_guild_leaderboard;
 
+// Results of finished periods stay in the channel and are never edited.
+async fn get_page_messages(ctx: &Context, leaderboard_channel: ChannelId) -> Result<Vec<Message>> {
+    let bot_id = ctx.cache.current_user_id();
+    let messages = leaderboard_channel.messages(&ctx, |m| m.limit(100)).await?;
+    Ok(messages
+        .into_iter()
+        .rev()
+        .filter(|m| m.author.id == bot_id && !m.content.starts_with(LEADERBOARD_ARCHIVE_HEADER))
+        .collect())
+}
+
+// The leaderboard is rendered from the database, so the messages are only ever
+// overwritten and never read back. Existing pages are edited in place, missing ones
+// are sent and the ones that are not needed anymore are deleted. Callers need to hold
+// the leaderboard lock of the guild and render the pages after taking it.
 pub async fn update_leaderboard(
     ctx: &Context,
     leaderboard_channel: ChannelId,
-    nickname: String,
-    time: (u8, u8),
+    pages: &[String],
 ) -> Result<()> {
-    let messages = leaderboard_channel.messages(&ctx, |m| m.limit(100)).await?;
-    if messages.is_empty() {
-        let leaderboard_content = format!(
-            "## Runner Leaderboard\n\n`{}`\t\t{}",
-            format_time(mins_secs_to_millis(time)),
-            nickname
-        );
-        leaderboard_channel
-            .send_message(&ctx.http, |m| m.content(leaderboard_content))
-            .await?;
-    } else {
-        let first_message_id = messages.last().unwrap().id;
-        let first_message = messages.last().unwrap().content.to_owned();
-        let leaderboard_lines = first_message
-            .split("\n")
-            .filter(|l| l != &"## Runner Leaderboard" && l != &"")
-            .collect::<Vec<&str>>();
-        let mut player_names_with_time: HashMap<String, u64> = HashMap::new();
-        for l in leaderboard_lines {
-            let splits = l.split("\t\t").collect::<Vec<&str>>();
-            if splits.len() != 2 {
-                return Err("LeaderboardError: parse leaderboard message.".into());
-            }
-            let player_name = splits[1];
-            let time = splits[0].replace("`", "");
-            let time_splits = time
-                .split(':')
-                .map(|sp| sp.parse::<u8>().unwrap())
-                .collect::<Vec<u8>>();
-            let (minutes, seconds) = (time_splits[0], time_splits[1]);
-            let time_millis: u64 = mins_secs_to_millis((minutes, seconds));
-            player_names_with_time.insert(player_name.to_owned(), time_millis);
+    let mut page_messages = get_page_messages(ctx, leaderboard_channel).await?;
+    if page_messages.len() > pages.len() {
+        for message in page_messages.split_off(pages.len()) {
+            leaderboard_channel
+                .delete_message(&ctx.http, message.id)
+                .await?;
         }
-        let current_finish_time = mins_secs_to_millis(time);
-        if player_names_with_time.get(&nickname).is_some() {
-            let time = player_names_with_time.get(&nickname).unwrap();
-            if time > &current_finish_time {
-                player_names_with_time.insert(nickname.to_owned(), current_finish_time);
+    }
+    for (idx, page) in pages.iter().enumerate() {
+        match page_messages.get(idx) {
+            Some(message) if &message.content == page => (),
+            Some(message) => {
+                leaderboard_channel
+                    .edit_message(&ctx, message.id, |m| m.content(page))
+                    .await?;
             }
-        } else {
-            player_names_with_time.insert(nickname, mins_secs_to_millis(time));
-        }
-        let mut entry_vector: Vec<(&String, &u64)> = player_names_with_time
-            .iter()
-            .collect::<Vec<(&String, &u64)>>();
-        entry_vector.sort_by(|a, b| a.1.cmp(b.1));
-        let mut updated_contents: Vec<String> = vec![];
-        for entry in entry_vector {
-            let name = entry.0;
-            let time = format_time(entry.1.to_owned());
-            updated_contents.push(format!("`{}`\t\t{}", time, name));
+            None => {
+                leaderboard_channel
+                    .send_message(&ctx.http, |m| m.content(page))
+                    .await?;
+            }
+        };
+    }
+    Ok(())
+}
+
+// Renders the leaderboard of a guild and writes it to its channel. The pages are only
+// rendered once the lock is taken, so the last update always leaves the latest board.
+// Returns false when the guild has no leaderboard.
+pub async fn refresh_leaderboard(
+    ctx: &Context,
+    guild_id: GuildId,
+    cache_manager: &ArcMutex<CacheManager>,
+) -> Result<bool> {
+    let leaderboard_lock = cache_manager.lock().await.leaderboard_lock(guild_id);
+    let _guard = leaderboard_lock.lock().await;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    let leaderboard = render_guild_leaderboard(&*cache_manager.lock().await, guild_id, now);
+    match leaderboard {
+        Some((channel_id, pages)) => {
+            update_leaderboard(ctx, channel_id, &pages).await?;
+            Ok(true)
         }
-        let leaderboard_content =
-            format!("## Runner Leaderboard\n\n{}", updated_contents.join("\n"));
+        None => Ok(false),
+    }
+}
+
+// The results are sent above a fresh leaderboard, so the old pages are removed first.
+// Like updates, archiving needs the leaderboard lock of the guild.
+pub async fn archive_leaderboard(
+    ctx: &Context,
+    leaderboard_channel: ChannelId,
+    archives: &[String],
+) -> Result<()> {
+    if archives.is_empty() {
+        return Ok(());
+    }
+    let page_messages = get_page_messages(ctx, leaderboard_channel).await?;
+    for message in page_messages {
+        leaderboard_channel
+            .delete_message(&ctx.http, message.id)
+            .await?;
+    }
+    for archive in archives {
         leaderboard_channel
-            .edit_message(&ctx, first_message_id, |m| m.content(leaderboard_content))
+            .send_message(&ctx.http, |m| m.content(archive))
             .await?;
     }
     Ok(())
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+use super::consts::{WS_BACKOFF_BASE_SECS, WS_BACKOFF_MAX_SECS};
+
+#[derive(Debug)]
+pub struct Backoff {
+    pub attempt: u32,
+}
+
+impl Backoff {
+    pub fn new() -> Self {
+        Self { attempt: 0 }
+    }
+
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+
+    pub fn max_delay(&self) -> Duration {
+        if self.attempt == 0 {
+            return Duration::ZERO;
+        }
+        let exponent = (self.attempt - 1).min(16);
+        let secs = WS_BACKOFF_BASE_SECS.saturating_mul(1 << exponent);
+        Duration::from_secs(secs.min(WS_BACKOFF_MAX_SECS))
+    }
+
+    pub fn next_delay(&mut self) -> Duration {
+        // The first retry after a healthy connection happens right away, every
+        // following one waits between half and all of the exponential delay.
+        let max_delay = self.max_delay();
+        self.attempt += 1;
+        if max_delay.is_zero() {
+            return max_delay;
+        }
+        let half_delay = max_delay / 2;
+        let jitter = rand::thread_rng().gen_range(0..=half_delay.as_millis() as u64);
+        half_delay + Duration::from_millis(jitter)
+    }
+}
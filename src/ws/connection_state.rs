@@ -0,0 +1,25 @@
+use std::{fmt, time::Duration};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionState {
+    Connecting { attempt: u32 },
+    Connected,
+    BackingOff { attempt: u32, retry_in: Duration },
+}
+
+impl fmt::Display for ConnectionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConnectionState::Connecting { attempt } => {
+                write!(f, "Connecting to paceman.gg (attempt {})", attempt + 1)
+            }
+            ConnectionState::Connected => write!(f, "Connected to paceman.gg"),
+            ConnectionState::BackingOff { attempt, retry_in } => write!(
+                f,
+                "Disconnected from paceman.gg, retrying in {} seconds (attempt {})",
+                retry_in.as_secs(),
+                attempt + 1
+            ),
+        }
+    }
+}
@@ -3,4 +3,12 @@ pub const WS_FALLBACK_URL: &'static str = "wss://paceman.gg/ws";
 pub const WS_UPGRADE_HEADER: &'static str = "websocket";
 pub const WS_CONNECTION_HEADER: &'static str = "upgrade";
 pub const WS_SEC_VERSION_HEADER: u64 = 13;
-pub const WS_TIMEOUT_FOR_RETRY: u64 = 5;
+pub const WS_BACKOFF_BASE_SECS: u64 = 1;
+pub const WS_BACKOFF_MAX_SECS: u64 = 300;
+pub const WS_DEFAULT_IDLE_TIMEOUT: u64 = 60;
+pub const WS_MIN_IDLE_TIMEOUT: u64 = 10;
+pub const REPLAY_DEFAULT_SPEED: f64 = 1.0;
+pub const REPLAY_CACHE_WAIT_SECS: u64 = 30;
+pub const QUARANTINE_FALLBACK_FILE: &str = "quarantine.jsonl";
+pub const QUARANTINE_MAX_BYTES: u64 = 5 * 1024 * 1024;
+pub const QUARANTINE_MAX_FILES: u32 = 3;
@@ -1,10 +1,25 @@
+use std::time::Duration;
+
+use tokio::fs::File;
+use tokio::io::{BufReader, Lines};
 use tokio::net::TcpStream;
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
 
+pub mod backoff;
+pub mod connection_state;
 pub mod consts;
+pub mod quarantine;
+pub mod replay;
 pub mod response;
 pub mod ws;
 
 pub struct WSManager {
     pub stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    pub idle_timeout: Duration,
+}
+
+pub struct ReplayManager {
+    pub lines: Lines<BufReader<File>>,
+    pub speed: f64,
+    pub last_updated: Option<i64>,
 }
@@ -0,0 +1,47 @@
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::json;
+
+use crate::Result;
+
+use super::consts::{QUARANTINE_FALLBACK_FILE, QUARANTINE_MAX_BYTES, QUARANTINE_MAX_FILES};
+
+fn rotate(path: &str) -> Result<()> {
+    let oldest = format!("{}.{}", path, QUARANTINE_MAX_FILES);
+    if Path::new(&oldest).exists() {
+        fs::remove_file(&oldest)?;
+    }
+    for idx in (1..QUARANTINE_MAX_FILES).rev() {
+        let from = format!("{}.{}", path, idx);
+        if Path::new(&from).exists() {
+            fs::rename(&from, format!("{}.{}", path, idx + 1))?;
+        }
+    }
+    fs::rename(path, format!("{}.1", path))?;
+    Ok(())
+}
+
+pub fn quarantine_payload(payload: &str, error: &str) -> Result<()> {
+    let path = env::var("QUARANTINE_FILE").unwrap_or(QUARANTINE_FALLBACK_FILE.to_string());
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+    let line = json!({
+        "timestamp": timestamp,
+        "error": error,
+        "payload": payload,
+    })
+    .to_string();
+    if let Ok(metadata) = fs::metadata(&path) {
+        if metadata.len() + line.len() as u64 > QUARANTINE_MAX_BYTES {
+            rotate(&path)?;
+        }
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{}", line)?;
+    Ok(())
+}
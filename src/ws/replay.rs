@@ -0,0 +1,77 @@
+use std::env;
+use std::time::Duration;
+
+use tokio::fs::File;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::time::sleep;
+
+use super::consts::REPLAY_DEFAULT_SPEED;
+use super::response::Response;
+use super::ReplayManager;
+
+impl ReplayManager {
+    pub fn is_enabled() -> bool {
+        env::var("REPLAY_FILE").is_ok()
+    }
+
+    async fn wait_for(&mut self, response: &Response) {
+        let previous = match self.last_updated.replace(response.last_updated) {
+            Some(previous) => previous,
+            None => return,
+        };
+        // A speed of 0 replays the file as fast as possible.
+        if self.speed <= 0.0 || response.last_updated <= previous {
+            return;
+        }
+        let delta_millis = (response.last_updated - previous) as f64 / self.speed;
+        sleep(Duration::from_millis(delta_millis as u64)).await;
+    }
+
+    pub async fn get_next(&mut self) -> Option<Response> {
+        loop {
+            let line = match self.lines.next_line().await {
+                Ok(Some(line)) => line,
+                Ok(None) => return None,
+                Err(err) => {
+                    eprintln!("ReplayManager get next error: read line due to: {}", err);
+                    return None;
+                }
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response: Response = match serde_json::from_str(line.as_str()) {
+                Ok(response) => response,
+                Err(err) => {
+                    eprintln!("ReplayManager get next error: JSONify error: {}", err);
+                    continue;
+                }
+            };
+            self.wait_for(&response).await;
+            return Some(response);
+        }
+    }
+
+    pub async fn new() -> std::result::Result<Self, String> {
+        let path = match env::var("REPLAY_FILE") {
+            Ok(path) => path,
+            Err(err) => return Err(format!("REPLAY_FILE not found in env: {}", err)),
+        };
+        let speed = match env::var("REPLAY_SPEED") {
+            Ok(speed) => match speed.parse::<f64>() {
+                Ok(speed) => speed,
+                Err(err) => return Err(format!("REPLAY_SPEED is not a number: {}", err)),
+            },
+            Err(_) => REPLAY_DEFAULT_SPEED,
+        };
+        let file = match File::open(&path).await {
+            Ok(file) => file,
+            Err(err) => return Err(format!("Open replay file: '{}' due to: {}", path, err)),
+        };
+        Ok(Self {
+            lines: BufReader::new(file).lines(),
+            speed,
+            last_updated: None,
+        })
+    }
+}
@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 pub enum EventId {
     #[serde(rename = "common.open_to_lan")]
     CommonOpenToLan,
@@ -56,9 +56,12 @@ pub enum EventId {
     RsgObtainBlazeRod,
     #[serde(rename = "rsg.kill_dragon")]
     RsgKillDragon,
+
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Event {
     pub event_id: EventId,
@@ -66,20 +69,21 @@ pub struct Event {
     pub igt: i64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum EventType {
     NonPaceEvent,
     PaceEvent,
+    Reset,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct User {
     pub uuid: String,
     pub live_account: Option<String>,
 }
 
-#[derive(Deserialize, Debug, Eq, PartialEq, Hash)]
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Hash, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum Item {
     #[serde(rename = "minecraft:ender_pearl")]
@@ -88,16 +92,18 @@ pub enum Item {
     MinecraftObsidian,
     #[serde(rename = "minecraft:blaze_rod")]
     MinecraftBlazeRod,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ItemData {
     pub estimated_counts: HashMap<Item, u32>,
     pub _usages: Option<HashMap<Item, u32>>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub game_version: Option<String>,
@@ -1,16 +1,19 @@
 use std::env;
 use std::time::Duration;
 
-use crate::ws::consts::{WS_FALLBACK_HOST, WS_FALLBACK_URL, WS_TIMEOUT_FOR_RETRY};
+use crate::ws::consts::{
+    WS_DEFAULT_IDLE_TIMEOUT, WS_FALLBACK_HOST, WS_FALLBACK_URL, WS_MIN_IDLE_TIMEOUT,
+};
 use crate::Result;
-use serenity::futures::StreamExt;
+use serenity::futures::{SinkExt, StreamExt};
 use tokio::net::TcpStream;
-use tokio::time::sleep;
+use tokio::time::timeout;
 use tokio_tungstenite::{
-    tungstenite::{handshake::client::generate_key, http::request},
+    tungstenite::{handshake::client::generate_key, http::request, Message},
     MaybeTlsStream, WebSocketStream,
 };
 
+use super::quarantine::quarantine_payload;
 use super::response::Response;
 use super::{
     consts::{WS_CONNECTION_HEADER, WS_SEC_VERSION_HEADER, WS_UPGRADE_HEADER},
@@ -18,46 +21,112 @@ use super::{
 };
 
 impl WSManager {
-    async fn print_err_and_sleep(response: String) {
+    fn print_err(response: String) {
         eprintln!("WSManager get next error: {}", response);
-        println!("Trying again in {} seconds...", WS_TIMEOUT_FOR_RETRY);
-        sleep(Duration::from_secs(WS_TIMEOUT_FOR_RETRY)).await;
     }
 
     pub async fn get_next(&mut self) -> Option<Response> {
-        let msg = match match self.stream.next().await {
-            Some(msg_result) => msg_result,
-            None => {
-                let response =
-                    "Get Message Result Error: Invalid response from response stream.".to_string();
-                Self::print_err_and_sleep(response).await;
-                return None;
-            }
-        } {
-            Ok(msg) => msg,
-            Err(err) => {
-                let response = format!("Get Message Error: {}", err);
-                Self::print_err_and_sleep(response).await;
-                return None;
-            }
-        };
-        let text_response = match msg.to_text() {
-            Ok(text) => text,
-            Err(err) => {
-                let response = format!("Get Text Response Error: {}", err);
-                Self::print_err_and_sleep(response).await;
-                return None;
-            }
-        };
-        let response: Response = match serde_json::from_str(text_response) {
-            Ok(response) => response,
-            Err(err) => {
-                let response = format!("JSONify error: {}", err);
-                Self::print_err_and_sleep(response).await;
-                return None;
-            }
+        let mut is_probing = false;
+        loop {
+            // Half of the idle window is spent waiting for data and the other half
+            // waiting for the pong of a probe, so a dead connection is noticed in time.
+            let msg_result = match timeout(self.idle_timeout / 2, self.stream.next()).await {
+                Ok(msg_result) => msg_result,
+                Err(_) => {
+                    if is_probing {
+                        eprintln!(
+                            "WSManager get next error: No data received for {} seconds. Reconnecting...",
+                            self.idle_timeout.as_secs()
+                        );
+                        return None;
+                    }
+                    if let Err(err) = self.stream.send(Message::Ping(vec![])).await {
+                        eprintln!("WSManager get next error: Send Ping Error: {}", err);
+                        return None;
+                    }
+                    is_probing = true;
+                    continue;
+                }
+            };
+            is_probing = false;
+            let msg = match msg_result {
+                Some(Ok(msg)) => msg,
+                Some(Err(err)) => {
+                    let response = format!("Get Message Error: {}", err);
+                    Self::print_err(response);
+                    return None;
+                }
+                None => {
+                    let response =
+                        "Get Message Result Error: Invalid response from response stream."
+                            .to_string();
+                    Self::print_err(response);
+                    return None;
+                }
+            };
+            let text_response = match msg {
+                Message::Text(text) => text,
+                Message::Binary(bytes) => match String::from_utf8(bytes) {
+                    Ok(text) => text,
+                    Err(err) => {
+                        eprintln!("WSManager get next error: Get Text Response Error: {}", err);
+                        continue;
+                    }
+                },
+                Message::Ping(_) => {
+                    // tungstenite queues the pong reply itself, flushing sends it right away.
+                    if let Err(err) = self.stream.flush().await {
+                        eprintln!("WSManager get next error: Send Pong Error: {}", err);
+                        return None;
+                    }
+                    continue;
+                }
+                Message::Pong(_) | Message::Frame(_) => continue,
+                Message::Close(frame) => {
+                    println!(
+                        "WSManager: Connection closed by server: {:?}. Reconnecting...",
+                        frame
+                    );
+                    return None;
+                }
+            };
+            let response: Response = match serde_json::from_str(text_response.as_str()) {
+                Ok(response) => response,
+                Err(err) => {
+                    let response = format!("JSONify error: {}", err);
+                    Self::print_err(response);
+                    match quarantine_payload(text_response.as_str(), err.to_string().as_str()) {
+                        Ok(_) => (),
+                        Err(err) => Self::print_err(format!("Quarantine error: {}", err)),
+                    };
+                    continue;
+                }
+            };
+            return Some(response);
+        }
+    }
+
+    // Very short timeouts would ping and reconnect without any pause, so they are
+    // raised to WS_MIN_IDLE_TIMEOUT.
+    fn get_idle_timeout() -> Duration {
+        let secs = match env::var("WS_IDLE_TIMEOUT") {
+            Ok(secs) => match secs.parse::<u64>() {
+                Ok(secs) if secs < WS_MIN_IDLE_TIMEOUT => {
+                    eprintln!(
+                        "WS_IDLE_TIMEOUT: {} is too short, using {} seconds instead.",
+                        secs, WS_MIN_IDLE_TIMEOUT
+                    );
+                    WS_MIN_IDLE_TIMEOUT
+                }
+                Ok(secs) => secs,
+                Err(err) => {
+                    eprintln!("WS_IDLE_TIMEOUT is not a number: {}", err);
+                    WS_DEFAULT_IDLE_TIMEOUT
+                }
+            },
+            Err(_) => WS_DEFAULT_IDLE_TIMEOUT,
         };
-        Some(response)
+        Duration::from_secs(secs)
     }
 
     async fn get_response_stream() -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>> {
@@ -104,6 +173,9 @@ impl WSManager {
                 return Err(response.into());
             }
         };
-        Ok(Self { stream })
+        Ok(Self {
+            stream,
+            idle_timeout: Self::get_idle_timeout(),
+        })
     }
 }